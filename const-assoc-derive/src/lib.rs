@@ -1,69 +1,504 @@
-use anyhow::{anyhow, bail, Result};
 use proc_macro::TokenStream;
-use proc_macro2::{Ident, TokenStream as TokenStream2};
+use proc_macro2::{Span, TokenStream as TokenStream2};
 use quote::{quote, ToTokens};
-use syn::{parse_macro_input, AttrStyle, Attribute, Data, DataEnum, DeriveInput};
+use std::collections::HashMap;
+use syn::punctuated::Punctuated;
+use syn::{
+    parse_macro_input, AttrStyle, Attribute, BinOp, Data, DataEnum, DeriveInput, Error, Expr,
+    ExprBinary, ExprCast, ExprGroup, ExprLit, ExprParen, ExprPath, Fields, Ident, Lit, LitInt,
+    Meta, Path, Result, Token, Type, TypePath,
+};
 
-#[proc_macro_derive(PrimitiveEnum)]
+/// Derives `PrimitiveEnum` for an enum with a primitive representation.
+///
+/// # Representation
+/// The `#[repr]` attributes of the enum must contain exactly one of `u8`,
+/// `u16`, `u32`, `u64` and `usize`, in any position. `C` is ignored, while
+/// `align(N)` is rejected since it makes the enum larger than its
+/// discriminant type. Any other hint is rejected as well.
+///
+/// # Attributes
+/// * `#[primitive_enum(const_default)]` - Also implements `ConstDefault`,
+///   using the variant with discriminant 0 as the default value.
+/// * `#[primitive_enum(from_repr)]` - Also generates an inherent
+///   `const fn from_repr(value: Repr) -> Option<Self>`, where `Repr` is the
+///   primitive representation of the enum. It returns the variant with the
+///   given discriminant, or `None` if there is no such variant.
+/// * `#[primitive_enum(variants = N)]` - States the number of variants of the
+///   enum, which must be `N`. This also allows discriminants that the derive
+///   can't evaluate, see below.
+/// * `#[primitive_enum(unsafe_repr = u8)]` - Uses the given primitive type as
+///   the discriminant type instead of reading it from `#[repr]`, for enums
+///   that can't carry a `#[repr]` attribute. This relies on rustc storing a
+///   fieldless enum with the default representation as its discriminant,
+///   which is not guaranteed by the language, hence the `unsafe_` prefix. The
+///   generated code checks this at compile time by reading every variant as
+///   the given type and comparing the result with its discriminant.
+///
+/// # Discriminants
+/// The discriminants of an enum with `N` variants must be exactly `0..N`, in
+/// any order, since an `Assoc` keyed by it stores one value per discriminant
+/// in that range. Bitflag-style discriminants such as `1 << n` are therefore
+/// not supported.
+///
+/// Explicit discriminants may be made of integer literals, arithmetic and
+/// bitwise operators, and casts of earlier variants of the same enum, written
+/// as `Self::Variant as T` or `Enum::Variant as T`, and are checked by the
+/// derive. Other expressions, like calls to `const fn`s, are only accepted
+/// together with `#[primitive_enum(variants = N)]`. Either way, the generated
+/// code asserts the real discriminants at compile time.
+///
+/// # `#[non_exhaustive]` enums
+/// The derive works on `#[non_exhaustive]` enums, but note that the number of
+/// values stored in an `Assoc` keyed by the enum is its number of variants, so
+/// adding a variant changes the size of every such map.
+#[proc_macro_derive(PrimitiveEnum, attributes(primitive_enum))]
 pub fn derive_primitive_enum(item: TokenStream) -> TokenStream {
     let input = parse_macro_input!(item as DeriveInput);
-    derive(&input).unwrap().into()
+    derive(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
 }
 
 fn derive(input: &DeriveInput) -> Result<TokenStream2> {
     let data = match &input.data {
         Data::Enum(data) => data,
-        _ => bail!("`PrimitiveEnum` only applies to enums"),
+        _ => {
+            return Err(Error::new_spanned(
+                &input.ident,
+                "`PrimitiveEnum` only applies to enums",
+            ))
+        }
     };
 
+    for variant in &data.variants {
+        if !matches!(variant.fields, Fields::Unit) {
+            return Err(Error::new_spanned(
+                variant,
+                "`PrimitiveEnum` requires fieldless variants",
+            ));
+        }
+    }
+
     let name = &input.ident;
-    let repr = parse_repr_attribute(&input.attrs)?;
-    let max_variants = max_discriminant(data)? + 1;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let options = parse_options(&input.attrs)?;
+    let has_unsafe_repr = options.unsafe_repr.is_some();
+    let repr = match options.unsafe_repr {
+        Some(repr) => repr,
+        None => parse_repr_attribute(&input.attrs)?,
+    };
+    let discriminants = discriminants(name, data, options.variants.is_some())?;
+    let max_variants = discriminants.len();
+
+    if let Some(variants) = &options.variants {
+        if variants.base10_parse::<usize>()? != max_variants {
+            return Err(Error::new_spanned(
+                variants,
+                format!("`variants` is {variants}, but the enum has {max_variants} variants"),
+            ));
+        }
+    }
+
+    let const_default = if options.const_default {
+        let default_variant = &data
+            .variants
+            .iter()
+            .zip(&discriminants)
+            .find(|(_, &discriminant)| discriminant == Some(0))
+            .ok_or_else(|| {
+                Error::new_spanned(
+                    &input.ident,
+                    "`const_default` requires the enum to have a variant with discriminant 0",
+                )
+            })?
+            .0
+            .ident;
+
+        quote! {
+            impl #impl_generics ::const_assoc::ConstDefault for #name #ty_generics #where_clause {
+                const DEFAULT: Self = Self::#default_variant;
+            }
+        }
+    } else {
+        TokenStream2::new()
+    };
+
+    let from_repr = if options.from_repr {
+        let variant_idents = data.variants.iter().map(|variant| &variant.ident);
+
+        quote! {
+            impl #impl_generics #name #ty_generics #where_clause {
+                /// Returns the variant with the given discriminant, or `None` if
+                /// there is no such variant.
+                #[allow(dead_code)]
+                pub const fn from_repr(value: #repr) -> ::core::option::Option<Self> {
+                    #(
+                        if value == Self::#variant_idents as #repr {
+                            return ::core::option::Option::Some(Self::#variant_idents);
+                        }
+                    )*
+
+                    ::core::option::Option::None
+                }
+            }
+        }
+    } else {
+        TokenStream2::new()
+    };
+
+    // The discriminants evaluated above are only used to report errors early,
+    // so assert the real ones as well.
+    let variant_idents = data.variants.iter().map(|variant| &variant.ident);
+    let messages = data.variants.iter().map(|variant| {
+        format!(
+            "the discriminant of `{name}::{}` must be less than {max_variants}",
+            variant.ident
+        )
+    });
+
+    // Without a `#[repr]`, the layout of the enum is unspecified, so check
+    // that every variant is stored as its discriminant.
+    let assert_layout = if has_unsafe_repr {
+        let variant_idents = data.variants.iter().map(|variant| &variant.ident);
+        let messages = data.variants.iter().map(|variant| {
+            format!(
+                "`{name}::{}` is not stored as its discriminant of type `{}`",
+                variant.ident,
+                repr.to_token_stream()
+            )
+        });
+        let size_message = format!(
+            "`{name}` doesn't have the size of `{}`",
+            repr.to_token_stream()
+        );
+
+        quote! {
+            assert!(
+                ::core::mem::size_of::<Self>() == ::core::mem::size_of::<#repr>(),
+                #size_message
+            );
+            #(
+                assert!(
+                    unsafe { ::core::mem::transmute_copy::<Self, #repr>(&Self::#variant_idents) }
+                        == Self::#variant_idents as #repr,
+                    #messages
+                );
+            )*
+        }
+    } else {
+        TokenStream2::new()
+    };
+
+    // A free constant can't name a generic enum, in which case the assertions
+    // are only evaluated once a key is converted to or from an index.
+    let assert_discriminants = if input.generics.params.is_empty() {
+        quote! {
+            const _: () = <#name as ::const_assoc::PrimitiveEnum>::ASSERT_DISCRIMINANTS;
+        }
+    } else {
+        TokenStream2::new()
+    };
 
     Ok(quote! {
-        unsafe impl ::const_assoc::PrimitiveEnum for #name {
+        unsafe impl #impl_generics ::const_assoc::PrimitiveEnum for #name #ty_generics #where_clause {
             type Layout = ::const_assoc::PrimitiveEnumLayout<#repr, #max_variants>;
+
+            const ASSERT_DISCRIMINANTS: () = {
+                #(
+                    assert!((Self::#variant_idents as u128) < #max_variants as u128, #messages);
+                )*
+
+                #assert_layout
+            };
         }
+
+        #assert_discriminants
+
+        #from_repr
+
+        #const_default
     })
 }
 
+#[derive(Default)]
+struct Options {
+    const_default: bool,
+    from_repr: bool,
+    unsafe_repr: Option<EnumRepr>,
+    variants: Option<LitInt>,
+}
+
+fn parse_options(attrs: &[Attribute]) -> Result<Options> {
+    let mut options = Options::default();
+
+    for attr in attrs {
+        if !attr.path().is_ident("primitive_enum") {
+            continue;
+        }
+
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("const_default") {
+                options.const_default = true;
+                Ok(())
+            } else if meta.path.is_ident("from_repr") {
+                options.from_repr = true;
+                Ok(())
+            } else if meta.path.is_ident("unsafe_repr") {
+                let ident: Ident = meta.value()?.parse()?;
+                let repr = EnumRepr::from_ident(&ident).ok_or_else(|| {
+                    Error::new_spanned(
+                        &ident,
+                        format!("`{ident}` is not supported as a primitive enum representation"),
+                    )
+                })?;
+                options.unsafe_repr = Some(repr);
+                Ok(())
+            } else if meta.path.is_ident("variants") {
+                options.variants = Some(meta.value()?.parse()?);
+                Ok(())
+            } else {
+                Err(meta.error("unsupported `primitive_enum` option"))
+            }
+        })?;
+    }
+
+    Ok(options)
+}
+
 fn parse_repr_attribute(attrs: &[Attribute]) -> Result<EnumRepr> {
-    let attr = attrs
-        .iter()
-        .find(|attr| {
-            let matching_ident = attr
-                .path()
-                .get_ident()
-                .map(|ident| *ident == "repr")
-                .unwrap_or(false);
-
-            matching_ident && matches!(attr.style, AttrStyle::Outer)
-        })
-        .ok_or(anyhow!("enum must have a primitive representation"))?;
-
-    let repr_ident = attr.meta.require_list()?.parse_args::<Ident>()?;
-    let repr_str = repr_ident.to_string();
-
-    let repr = match repr_str.as_str() {
-        "u8" => EnumRepr::U8,
-        "u16" => EnumRepr::U16,
-        "u32" => EnumRepr::U32,
-        "u64" => EnumRepr::U64,
-        "usize" => EnumRepr::USize,
-        _ => bail!("`{repr_str}` is not supported as a primitive enum representation"),
-    };
+    let mut repr = None;
+
+    for attr in attrs {
+        let matching_ident = attr
+            .path()
+            .get_ident()
+            .map(|ident| *ident == "repr")
+            .unwrap_or(false);
+
+        if !matching_ident || !matches!(attr.style, AttrStyle::Outer) {
+            continue;
+        }
+
+        let hints = attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)?;
 
-    Ok(repr)
+        for hint in hints {
+            let hint_str = hint.path().to_token_stream().to_string();
+
+            let hint_repr = match hint.path().get_ident().and_then(EnumRepr::from_ident) {
+                Some(hint_repr) => hint_repr,
+                // `C` doesn't change the layout of an enum with an explicit
+                // primitive representation.
+                None if hint_str == "C" => continue,
+                None if hint_str == "align" => {
+                    return Err(Error::new_spanned(
+                        hint,
+                        "`align` is not supported since it changes the size of the enum",
+                    ))
+                }
+                None => {
+                    return Err(Error::new_spanned(
+                        hint,
+                        format!("`{hint_str}` is not supported as a primitive enum representation"),
+                    ))
+                }
+            };
+
+            if repr.replace(hint_repr).is_some() {
+                return Err(Error::new_spanned(
+                    hint,
+                    "enum must have exactly one primitive representation",
+                ));
+            }
+        }
+    }
+
+    repr.ok_or_else(|| {
+        Error::new(
+            Span::call_site(),
+            "enum must have a primitive representation",
+        )
+    })
 }
 
-fn max_discriminant(enum_: &DataEnum) -> Result<usize> {
+/// Evaluates the discriminant of every variant, in declaration order.
+///
+/// Discriminants that can't be evaluated are `None`, which is only allowed if
+/// `allow_unknown` is set.
+fn discriminants(name: &Ident, enum_: &DataEnum, allow_unknown: bool) -> Result<Vec<Option<u128>>> {
+    let variants = enum_.variants.len();
+    let mut known = HashMap::new();
+    let mut values = Vec::with_capacity(variants);
+    let mut next = Some(0u128);
+
     for variant in &enum_.variants {
-        if variant.discriminant.is_some() {
-            bail!("enums that have variants with explicit discriminants are not supported");
+        let discriminant = match &variant.discriminant {
+            Some((_, expr)) => {
+                let discriminant = eval_discriminant(expr, name, &known)?;
+
+                if discriminant.is_none() && !allow_unknown {
+                    return Err(Error::new_spanned(
+                        expr,
+                        format!(
+                            "discriminant `{}` is too complex to evaluate, use an integer \
+                             literal instead or specify the number of variants with \
+                             `#[primitive_enum(variants = N)]` to have it checked at compile \
+                             time",
+                            expr.to_token_stream()
+                        ),
+                    ));
+                }
+
+                discriminant
+            }
+            None => next,
+        };
+
+        // `PrimitiveEnum` requires the discriminants to be exactly
+        // `0..VARIANTS`, which, given that the compiler ensures they are
+        // distinct, is the case if all of them are in that range.
+        if let Some(discriminant) = discriminant {
+            if discriminant >= variants as u128 {
+                return Err(Error::new_spanned(
+                    variant,
+                    format!(
+                        "the discriminant of `{}` is {discriminant}, but the discriminants of \
+                         an enum with {variants} variants must be exactly 0..{variants}",
+                        variant.ident
+                    ),
+                ));
+            }
+
+            known.insert(variant.ident.to_string(), discriminant);
         }
+
+        values.push(discriminant);
+        next = discriminant.map(|discriminant| discriminant + 1);
+    }
+
+    Ok(values)
+}
+
+/// Evaluates a discriminant expression made of integer literals, arithmetic and
+/// bitwise operators, and casts of previously declared variants of the enum
+/// named `name`. Returns `None` if the expression is too complex to evaluate.
+///
+/// Casts are evaluated without truncating to their target type, so the result
+/// may differ from the real discriminant. The generated code asserts the real
+/// discriminants, so this is only used to report errors early.
+fn eval_discriminant(
+    expr: &Expr,
+    name: &Ident,
+    known: &HashMap<String, u128>,
+) -> Result<Option<u128>> {
+    match expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Int(lit), ..
+        }) => Ok(Some(lit.base10_parse()?)),
+        Expr::Paren(ExprParen { expr, .. }) | Expr::Group(ExprGroup { expr, .. }) => {
+            eval_discriminant(expr, name, known)
+        }
+        Expr::Cast(ExprCast { expr, ty, .. }) => {
+            let value = match &**expr {
+                Expr::Path(ExprPath { path, .. }) => {
+                    let variant = own_variant(path, name).ok_or_else(|| {
+                        Error::new_spanned(
+                            path,
+                            format!(
+                                "only casts of variants of this enum, written as `Self::Variant` \
+                                 or `{name}::Variant`, are supported in discriminants"
+                            ),
+                        )
+                    })?;
+
+                    known.get(&variant.to_string()).copied()
+                }
+                expr => eval_discriminant(expr, name, known)?,
+            };
+
+            Ok(value.and_then(|value| eval_cast(value, ty)))
+        }
+        Expr::Binary(ExprBinary {
+            left, op, right, ..
+        }) => {
+            let (Some(left), Some(right)) = (
+                eval_discriminant(left, name, known)?,
+                eval_discriminant(right, name, known)?,
+            ) else {
+                return Ok(None);
+            };
+
+            let value = match op {
+                BinOp::Add(_) => left.checked_add(right),
+                BinOp::Sub(_) => left.checked_sub(right),
+                BinOp::Mul(_) => left.checked_mul(right),
+                BinOp::Shl(_) => u32::try_from(right).ok().and_then(|r| left.checked_shl(r)),
+                BinOp::Shr(_) => u32::try_from(right).ok().and_then(|r| left.checked_shr(r)),
+                BinOp::BitOr(_) => Some(left | right),
+                BinOp::BitAnd(_) => Some(left & right),
+                BinOp::BitXor(_) => Some(left ^ right),
+                _ => return Ok(None),
+            };
+
+            value.map(Some).ok_or_else(|| {
+                Error::new_spanned(
+                    expr,
+                    format!("discriminant `{}` overflows", expr.to_token_stream()),
+                )
+            })
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Evaluates `value as ty`, or returns `None` if `ty` is not a primitive
+/// integer type or the result is negative or depends on the target.
+fn eval_cast(value: u128, ty: &Type) -> Option<u128> {
+    let Type::Path(TypePath { qself: None, path }) = ty else {
+        return None;
+    };
+
+    let value = match path.get_ident()?.to_string().as_str() {
+        "u8" => u128::from(value as u8),
+        "u16" => u128::from(value as u16),
+        "u32" => u128::from(value as u32),
+        "u64" => u128::from(value as u64),
+        "u128" => value,
+        "i8" => u128::try_from(value as i8).ok()?,
+        "i16" => u128::try_from(value as i16).ok()?,
+        "i32" => u128::try_from(value as i32).ok()?,
+        "i64" => u128::try_from(value as i64).ok()?,
+        "i128" => u128::try_from(value as i128).ok()?,
+        // The width of these depends on the target, but they have at least
+        // 16 bits on every target Rust supports.
+        "usize" => u128::from(u16::try_from(value).ok()?),
+        "isize" => i16::try_from(value).ok()? as u128,
+        _ => return None,
+    };
+
+    Some(value)
+}
+
+/// Returns the variant named by `path` if it is `Self::Variant` or
+/// `Name::Variant`, where `Name` is `name`.
+fn own_variant<'a>(path: &'a Path, name: &Ident) -> Option<&'a Ident> {
+    if path.leading_colon.is_some()
+        || path.segments.len() != 2
+        || path
+            .segments
+            .iter()
+            .any(|segment| !segment.arguments.is_none())
+    {
+        return None;
     }
 
-    Ok(enum_.variants.len() - 1)
+    let enum_ = &path.segments[0].ident;
+
+    if enum_ == "Self" || enum_ == name {
+        Some(&path.segments[1].ident)
+    } else {
+        None
+    }
 }
 
 enum EnumRepr {
@@ -74,6 +509,19 @@ enum EnumRepr {
     USize,
 }
 
+impl EnumRepr {
+    fn from_ident(ident: &Ident) -> Option<Self> {
+        match ident.to_string().as_str() {
+            "u8" => Some(EnumRepr::U8),
+            "u16" => Some(EnumRepr::U16),
+            "u32" => Some(EnumRepr::U32),
+            "u64" => Some(EnumRepr::U64),
+            "usize" => Some(EnumRepr::USize),
+            _ => None,
+        }
+    }
+}
+
 impl ToTokens for EnumRepr {
     fn to_tokens(&self, tokens: &mut TokenStream2) {
         let t = match self {
@@ -83,7 +531,59 @@ impl ToTokens for EnumRepr {
             EnumRepr::U64 => quote! { u64 },
             EnumRepr::USize => quote! { usize },
         };
-        
+
         t.to_tokens(tokens);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    fn repr_of(input: DeriveInput) -> Result<EnumRepr> {
+        parse_repr_attribute(&input.attrs)
+    }
+
+    #[test]
+    fn repr_c_before_integer() {
+        let repr = repr_of(parse_quote! {
+            #[repr(C, u8)]
+            enum E { A }
+        });
+
+        assert!(matches!(repr, Ok(EnumRepr::U8)));
+    }
+
+    #[test]
+    fn repr_c_in_separate_attribute() {
+        let repr = repr_of(parse_quote! {
+            #[repr(C)]
+            #[repr(u16)]
+            enum E { A }
+        });
+
+        assert!(matches!(repr, Ok(EnumRepr::U16)));
+    }
+
+    #[test]
+    fn repr_integer_before_c() {
+        let repr = repr_of(parse_quote! {
+            #[repr(u8, C)]
+            enum E { A }
+        });
+
+        assert!(matches!(repr, Ok(EnumRepr::U8)));
+    }
+
+    #[test]
+    fn repr_integer_in_first_attribute() {
+        let repr = repr_of(parse_quote! {
+            #[repr(u32)]
+            #[repr(C)]
+            enum E { A }
+        });
+
+        assert!(matches!(repr, Ok(EnumRepr::U32)));
+    }
+}
@@ -0,0 +1,106 @@
+use crate::utils::TransmuteSafe;
+use crate::{Key, KeyImpl};
+use core::fmt::{self, Debug, Formatter};
+
+/// A key type for a contiguous range of `LEN` ASCII characters, starting at
+/// the character with code `LO`.
+///
+/// An [`Assoc`](crate::Assoc) keyed by `AsciiRange<LO, LEN>` stores exactly
+/// `LEN` values, one per character in the range. `LO + LEN` must not exceed
+/// 128, which is checked at compile time whenever a key is created or looked
+/// up.
+///
+/// # Example
+/// ```
+/// use const_assoc::{AsciiRange, Assoc};
+///
+/// type Lowercase = AsciiRange<b'a', 26>;
+///
+/// let mut counts = Assoc::<Lowercase, u32>::splat(0);
+///
+/// for c in "hello".chars() {
+///     counts[Lowercase::from_char(c).unwrap()] += 1;
+/// }
+///
+/// assert_eq!(counts[Lowercase::from_char('l').unwrap()], 2);
+/// assert_eq!(Lowercase::from_char('A'), None);
+/// ```
+#[repr(transparent)]
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AsciiRange<const LO: u8, const LEN: usize> {
+    // The offset of the character from `LO`, always less than `LEN`.
+    offset: u8,
+}
+
+impl<const LO: u8, const LEN: usize> AsciiRange<LO, LEN> {
+    /// Returns the key for the given byte, or `None` if it is outside of the
+    /// range.
+    #[inline(always)]
+    pub const fn new(byte: u8) -> Option<Self> {
+        let () = <Self as KeyImpl>::ASSERT_VALID;
+
+        if byte >= LO && ((byte - LO) as usize) < LEN {
+            Some(Self { offset: byte - LO })
+        } else {
+            None
+        }
+    }
+
+    /// Returns the key for the given character, or `None` if it is outside of
+    /// the range.
+    #[inline(always)]
+    pub const fn from_char(c: char) -> Option<Self> {
+        if c.is_ascii() {
+            Self::new(c as u8)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the byte this key represents.
+    #[inline(always)]
+    pub const fn byte(self) -> u8 {
+        LO + self.offset
+    }
+
+    /// Returns the character this key represents.
+    #[inline(always)]
+    pub const fn to_char(self) -> char {
+        self.byte() as char
+    }
+}
+
+impl<const LO: u8, const LEN: usize> Debug for AsciiRange<LO, LEN> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("AsciiRange").field(&self.to_char()).finish()
+    }
+}
+
+// SAFETY: Transmuting a type to itself is always safe.
+unsafe impl<const LO: u8, const LEN: usize> TransmuteSafe<AsciiRange<LO, LEN>>
+    for AsciiRange<LO, LEN>
+{
+}
+
+// SAFETY: `AsciiRange` is a `#[repr(transparent)]` wrapper around `u8`.
+unsafe impl<const LO: u8, const LEN: usize> TransmuteSafe<u8> for AsciiRange<LO, LEN> {}
+
+impl<const LO: u8, const LEN: usize> Key for AsciiRange<LO, LEN> {
+    type Impl = Self;
+}
+
+// SAFETY: `offset` is always less than `LEN`, and every `u8` less than `LEN`
+// is a valid offset. `ASSERT_VALID` is evaluated by `new` and by every
+// conversion between keys and indices, and fails to compile unless
+// `LO + LEN <= 128`. Hence, no key can exist and no index can be turned into
+// a key otherwise, and when it holds, any `usize` less than `LEN` fits into a
+// `u8` and `LO + offset` can't overflow.
+unsafe impl<const LO: u8, const LEN: usize> KeyImpl for AsciiRange<LO, LEN> {
+    type Storage<V> = [V; LEN];
+    type Repr = u8;
+
+    const ASSERT_VALID: () = assert!(
+        LO as usize + LEN <= 128,
+        "`AsciiRange` must only contain ASCII characters"
+    );
+}
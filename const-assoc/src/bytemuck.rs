@@ -0,0 +1,47 @@
+use crate::{Assoc, Key, KeyImpl};
+use ::bytemuck::{Pod, Zeroable};
+use core::mem;
+
+impl<K: Key, V: Pod, const N: usize> Assoc<K, V>
+where
+    K::Impl: KeyImpl<Storage<V> = [V; N]>,
+{
+    /// Reinterprets each value as a value of type `U`, keeping the same keys.
+    ///
+    /// Fails to compile if `V` and `U` have different sizes.
+    pub fn cast_values<U: Pod>(self) -> Assoc<K, U>
+    where
+        K::Impl: KeyImpl<Storage<U> = [U; N]>,
+    {
+        const { assert!(mem::size_of::<V>() == mem::size_of::<U>()) };
+        Assoc::from_values(self.storage.map(::bytemuck::cast))
+    }
+
+    /// Returns the values of this map, ordered by key discriminant, as bytes.
+    pub fn as_bytes(&self) -> &[u8] {
+        ::bytemuck::cast_slice(&self.storage)
+    }
+
+    /// Reinterprets `bytes` as a map, with values ordered by key discriminant.
+    ///
+    /// Returns `None` if `bytes` is not suitably aligned for `V` or does not
+    /// have the size of exactly `N` values.
+    pub fn try_from_bytes(bytes: &[u8]) -> Option<&Self> {
+        let values = ::bytemuck::try_cast_slice(bytes).ok()?;
+        Some(Self::from_array_ref(values.try_into().ok()?))
+    }
+}
+
+impl<K: Key, V: Zeroable, const N: usize> Assoc<K, V>
+where
+    K::Impl: KeyImpl<Storage<V> = [V; N]>,
+{
+    /// Creates a map where every value is all zeroes.
+    ///
+    /// Unlike `ConstDefault::DEFAULT`, this only requires `V: Zeroable`, which
+    /// guarantees that the all-zero bit pattern is a valid `V`.
+    pub const fn zeroed() -> Self {
+        // SAFETY: `V: Zeroable`, so an array of all-zero `V`s is valid.
+        Self::from_values(unsafe { mem::zeroed() })
+    }
+}
@@ -0,0 +1,39 @@
+use core::array;
+use core::iter::FusedIterator;
+
+/// An iterator over the values of an [`Assoc`](crate::Assoc), ordered by key
+/// discriminant.
+///
+/// This `struct` is created by `Assoc::into_values`.
+#[derive(Clone, Debug)]
+pub struct IntoValues<V, const N: usize>(pub(crate) array::IntoIter<V, N>);
+
+impl<V, const N: usize> Iterator for IntoValues<V, N> {
+    type Item = V;
+
+    #[inline]
+    fn next(&mut self) -> Option<V> {
+        self.0.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<V, const N: usize> DoubleEndedIterator for IntoValues<V, N> {
+    #[inline]
+    fn next_back(&mut self) -> Option<V> {
+        self.0.next_back()
+    }
+}
+
+impl<V, const N: usize> ExactSizeIterator for IntoValues<V, N> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+}
+
+impl<V, const N: usize> FusedIterator for IntoValues<V, N> {}
@@ -3,7 +3,8 @@
 
 //! A `no_std`-compatible, const-capable associative array with minimal or no runtime overhead.
 //!
-//! Currently, keys are limited to enums with a primitive representation. In the future,
+//! Currently, keys are limited to enums with a primitive representation and
+//! contiguous ranges of ASCII characters ([`AsciiRange`]). In the future,
 //! it might be possible to support other types, possibly at the expense of not exposing
 //! `const`-qualified methods for these key types or some runtime overhead.
 //!
@@ -29,16 +30,43 @@
 //! assert_eq!(letters[Letter::C], 'c');
 //! ```
 
+mod ascii;
+mod iter;
+mod num;
 mod utils;
 
+#[cfg(feature = "bytemuck")]
+mod bytemuck;
+
+#[cfg(feature = "proptest")]
+pub mod proptest;
+
+#[cfg(feature = "rkyv")]
+mod rkyv;
+
+#[cfg(feature = "rayon")]
+mod rayon;
+
+#[cfg(feature = "memchr")]
+mod memchr;
+
+use crate::num::{const_add, const_mul};
 use crate::utils::{
-    assume_init_array, into_usize, transmute_safe, ConstIntoUSize, ConstUSize, Is, IsConstUSize,
-    TransmuteSafe,
+    assume_init_array, from_usize, into_usize, transmute_safe, transmute_unchecked,
+    try_collect_array, ConstIntoUSize, ConstUSize, Is, IsConstUSize, TransmuteSafe,
 };
+use core::cmp::Ordering;
+use core::fmt::{self, Debug, Display, Formatter};
+use core::iter::{Product, Rev, Sum};
 use core::marker::PhantomData;
-use core::mem::MaybeUninit;
+use core::mem::{self, MaybeUninit};
 use core::ops::{Index, IndexMut};
 use derive_where::derive_where;
+use sealed::sealed;
+
+pub use crate::ascii::AsciiRange;
+pub use crate::iter::IntoValues;
+pub use crate::num::{ConstNum, SaturatingOps};
 
 // Re-export `const_default::ConstDefault`.
 pub use const_default::ConstDefault;
@@ -97,10 +125,78 @@ macro_rules! assoc {
     };
 }
 
+/// Constructs a new [`Assoc`] instance by calling a function with each key,
+/// from the lowest discriminant up to the highest.
+///
+/// Unlike `Assoc::from_fn`, this can be used in const contexts when given a
+/// `const fn`, since calling function pointers or closures isn't possible
+/// there.
+///
+/// # Example
+/// ```
+/// use const_assoc::{assoc_from_fn, Assoc, PrimitiveEnum};
+///
+/// #[repr(u8)]
+/// #[derive(Copy, Clone, PrimitiveEnum)]
+/// enum Letter {
+///     A,
+///     B,
+///     C,
+/// }
+///
+/// const fn lowercase(letter: Letter) -> char {
+///     match letter {
+///         Letter::A => 'a',
+///         Letter::B => 'b',
+///         Letter::C => 'c',
+///     }
+/// }
+///
+/// const LETTERS: Assoc<Letter, char> = assoc_from_fn!(lowercase);
+///
+/// assert_eq!(LETTERS[Letter::B], 'b');
+/// ```
+#[macro_export]
+macro_rules! assoc_from_fn {
+    ($f:expr) => {{
+        let mut map = $crate::Assoc::<_, _>::new_uninit();
+        let mut idx = 0;
+
+        while idx < $crate::assoc_macro_private::uninit_len(&map) {
+            // SAFETY: `idx` is less than the number of keys.
+            let key = unsafe { $crate::assoc_macro_private::index_to_key(idx) };
+            let value = $f(key);
+            *map.const_get_mut(key) = ::core::mem::MaybeUninit::new(value);
+            idx += 1;
+        }
+
+        // SAFETY: The loop above assigns a value to every key.
+        unsafe { map.assume_init() }
+    }};
+}
+
 #[doc(hidden)]
 pub mod assoc_macro_private {
-    use crate::{key_to_index, Key, KeyImpl};
+    use crate::{key_to_index, Assoc, Key, KeyImpl};
     use core::marker::PhantomData;
+    use core::mem::MaybeUninit;
+
+    /// # Safety
+    /// See `crate::index_to_key`.
+    #[inline(always)]
+    pub const unsafe fn index_to_key<K: Key>(idx: usize) -> K {
+        // SAFETY: Upheld by the caller.
+        unsafe { crate::index_to_key(idx) }
+    }
+
+    #[inline(always)]
+    pub const fn uninit_len<K: Key, V, const N: usize>(_map: &Assoc<K, MaybeUninit<V>>) -> usize
+    where
+        K::Impl: KeyImpl<Storage<V> = [V; N]>,
+        K::Impl: KeyImpl<Storage<MaybeUninit<V>> = [MaybeUninit<V>; N]>,
+    {
+        N
+    }
 
     pub const fn has_duplicate_keys<K: Key, V, const N: usize>(
         keys: &[K; N],
@@ -112,6 +208,14 @@ pub mod assoc_macro_private {
         let mut i = 0;
 
         while i < N {
+            // `const_get_mut` would panic on an out-of-range key anyway, but
+            // this points at the actual culprit when testing hand-written
+            // implementations.
+            debug_assert!(
+                key_to_index(keys[i]) < N,
+                "the key's index is out of range, its `PrimitiveEnum` implementation is unsound"
+            );
+
             let mut j = i + 1;
 
             while j < N {
@@ -153,15 +257,90 @@ where
     };
 }
 
-impl<K: Key, V: ConstDefault> Default for Assoc<K, V>
+impl<K: Key, V: Default, const N: usize> Default for Assoc<K, V>
 where
-    <K::Impl as KeyImpl>::Storage<V>: Default,
+    K::Impl: KeyImpl<Storage<V> = [V; N]>,
 {
     fn default() -> Self {
+        // `[V; N]` only implements `Default` for `N <= 32`, so build the
+        // storage element by element instead.
+        Self::from_fn(|_| V::default())
+    }
+}
+
+impl<K: Key, V> Clone for Assoc<K, V>
+where
+    <K::Impl as KeyImpl>::Storage<V>: Clone,
+{
+    fn clone(&self) -> Self {
         Self {
-            storage: <<K::Impl as KeyImpl>::Storage<V> as Default>::default(),
+            storage: self.storage.clone(),
         }
     }
+
+    /// Clones each value in place via `V::clone_from`, allowing values such
+    /// as `String` or `Vec` to reuse their existing allocations.
+    fn clone_from(&mut self, source: &Self) {
+        self.storage.clone_from(&source.storage);
+    }
+}
+
+impl<K: Key, V> Copy for Assoc<K, V> where <K::Impl as KeyImpl>::Storage<V>: Copy {}
+
+impl<K: Key, V: PartialEq<U>, U, const N: usize> PartialEq<Assoc<K, U>> for Assoc<K, V>
+where
+    K::Impl: KeyImpl<Storage<V> = [V; N]>,
+    K::Impl: KeyImpl<Storage<U> = [U; N]>,
+{
+    fn eq(&self, other: &Assoc<K, U>) -> bool {
+        self.storage == other.storage
+    }
+}
+
+impl<K: Key, V: Eq, const N: usize> Eq for Assoc<K, V> where K::Impl: KeyImpl<Storage<V> = [V; N]> {}
+
+/// Compares the values of the maps lexicographically, ordered by key
+/// discriminant.
+impl<K: Key, V: PartialOrd, const N: usize> PartialOrd for Assoc<K, V>
+where
+    K::Impl: KeyImpl<Storage<V> = [V; N]>,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.storage.partial_cmp(&other.storage)
+    }
+}
+
+/// Compares the values of the maps lexicographically, ordered by key
+/// discriminant.
+impl<K: Key, V: Ord, const N: usize> Ord for Assoc<K, V>
+where
+    K::Impl: KeyImpl<Storage<V> = [V; N]>,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.storage.cmp(&other.storage)
+    }
+}
+
+/// Compares the values of the map, ordered by key discriminant, against the
+/// elements of the array.
+impl<K: Key, V: PartialEq<U>, U, const N: usize> PartialEq<[U; N]> for Assoc<K, V>
+where
+    K::Impl: KeyImpl<Storage<V> = [V; N]>,
+{
+    fn eq(&self, other: &[U; N]) -> bool {
+        self.storage == *other
+    }
+}
+
+/// Compares the elements of the array against the values of the map,
+/// ordered by key discriminant.
+impl<K: Key, V: PartialEq<U>, U, const N: usize> PartialEq<Assoc<K, U>> for [V; N]
+where
+    K::Impl: KeyImpl<Storage<U> = [U; N]>,
+{
+    fn eq(&self, other: &Assoc<K, U>) -> bool {
+        *self == other.storage
+    }
 }
 
 impl<K: Key, V, const N: usize> Assoc<K, V>
@@ -170,10 +349,138 @@ where
 {
     pub const LEN: usize = N;
 
+    /// Creates a map where `values[i]` is associated with the key whose
+    /// discriminant is `i`.
+    ///
+    /// # Example
+    /// ```
+    /// use const_assoc::{Assoc, PrimitiveEnum};
+    ///
+    /// #[repr(u8)]
+    /// #[derive(Copy, Clone, PrimitiveEnum)]
+    /// enum Letter {
+    ///     B = 1,
+    ///     A = 0,
+    ///     C = 2,
+    /// }
+    ///
+    /// const LETTERS: Assoc<Letter, char> = Assoc::from_values(['a', 'b', 'c']);
+    ///
+    /// assert_eq!(LETTERS[Letter::A], 'a');
+    /// assert_eq!(LETTERS[Letter::B], 'b');
+    /// assert_eq!(LETTERS[Letter::C], 'c');
+    /// ```
     pub const fn from_values(values: [V; N]) -> Self {
         Self { storage: values }
     }
 
+    /// Reinterprets a reference to an array of values, ordered by key
+    /// discriminant, as a reference to a map, without copying.
+    #[inline(always)]
+    pub const fn from_array_ref(values: &[V; N]) -> &Self {
+        // SAFETY: `Assoc<K, V>` is a `#[repr(transparent)]` wrapper around
+        // `[V; N]`.
+        unsafe { &*(values as *const [V; N] as *const Self) }
+    }
+
+    /// Reinterprets a mutable reference to an array of values, ordered by key
+    /// discriminant, as a mutable reference to a map, without copying.
+    #[inline(always)]
+    pub const fn from_array_mut(values: &mut [V; N]) -> &mut Self {
+        // SAFETY: `Assoc<K, V>` is a `#[repr(transparent)]` wrapper around
+        // `[V; N]`.
+        unsafe { &mut *(values as *mut [V; N] as *mut Self) }
+    }
+
+    /// Creates a map where every key is associated with a copy of `value`.
+    ///
+    /// Unlike `ConstDefault::DEFAULT`, this doesn't require `V: ConstDefault`
+    /// and can be used in const contexts with any value.
+    pub const fn splat(value: V) -> Self
+    where
+        V: Copy,
+    {
+        Self::from_values([value; N])
+    }
+
+    /// Creates a map by calling `f` with each key, from the lowest
+    /// discriminant up to the highest.
+    pub fn from_fn(mut f: impl FnMut(K) -> V) -> Self {
+        Self::from_values(core::array::from_fn(|idx| {
+            // SAFETY: `idx` is less than `N`, since it is an index into `[V; N]`.
+            let key = unsafe { index_to_key(idx) };
+            f(key)
+        }))
+    }
+
+    /// Creates a map by calling `f` with each key, from the highest
+    /// discriminant down to the lowest.
+    ///
+    /// If `f` panics, the values it has produced so far are leaked.
+    pub fn from_fn_rev(mut f: impl FnMut(K) -> V) -> Self {
+        let mut values = [const { MaybeUninit::uninit() }; N];
+
+        for idx in (0..N).rev() {
+            // SAFETY: `idx` is less than `N`.
+            let key = unsafe { index_to_key(idx) };
+            values[idx].write(f(key));
+        }
+
+        // SAFETY: The loop above initializes every element of `values`.
+        Self::from_values(unsafe { assume_init_array(values) })
+    }
+
+    /// Creates a map where every value is `V::DEFAULT`, then lets `f` modify
+    /// it before returning it.
+    pub fn from_default_then(f: impl FnOnce(&mut Self)) -> Self
+    where
+        V: ConstDefault,
+    {
+        let mut map = Self::from_values([V::DEFAULT; N]);
+        f(&mut map);
+        map
+    }
+
+    /// Creates a map from key-value pairs, failing if any key is missing or
+    /// appears more than once.
+    ///
+    /// Unlike `TryFrom<&[(K, V)]>`, this takes the values by value and the
+    /// returned error reports every missing and every duplicated key.
+    pub fn from_entries_checked(
+        entries: impl IntoIterator<Item = (K, V)>,
+    ) -> Result<Self, MapBuildError<K>>
+    where
+        K::Impl: KeyImpl<Storage<Option<V>> = [Option<V>; N]>,
+        K::Impl: KeyImpl<Storage<bool> = [bool; N]>,
+    {
+        let mut values = Assoc::<K, Option<V>>::from_fn(|_| None);
+        let mut duplicated = Assoc::<K, bool>::splat(false);
+
+        for (key, value) in entries {
+            if values[key].replace(value).is_some() {
+                duplicated[key] = true;
+            }
+        }
+
+        let missing = values.map_ref(Option::is_none);
+
+        if missing
+            .values()
+            .chain(duplicated.values())
+            .any(|&flag| flag)
+        {
+            return Err(MapBuildError {
+                missing,
+                duplicated,
+            });
+        }
+
+        Ok(values.map_values(|value| {
+            // SAFETY: No key is missing, so every value is `Some`.
+            unsafe { value.unwrap_unchecked() }
+        }))
+    }
+
     #[inline(always)]
     pub const fn len(&self) -> usize {
         Self::LEN
@@ -197,9 +504,14 @@ where
     ///
     /// This version does bounds-checking therefore can be used in const
     /// contexts, unlike `get`.
+    ///
+    /// # Panics
+    /// Panics if the `PrimitiveEnum` implementation of `K` is unsound and
+    /// gives `key` an out-of-range discriminant.
     #[inline(always)]
     pub const fn const_get(&self, key: K) -> &V {
         let idx = key_to_index(key);
+        assert!(idx < N, "invalid discriminant for key type");
         &self.storage[idx]
     }
 
@@ -216,147 +528,1529 @@ where
     ///
     /// This version does bounds-checking, therefore can be used in const
     /// contexts, unlike `get`.
+    ///
+    /// # Panics
+    /// Panics if the `PrimitiveEnum` implementation of `K` is unsound and
+    /// gives `key` an out-of-range discriminant.
     #[inline(always)]
     pub const fn const_get_mut(&mut self, key: K) -> &mut V {
         let idx = key_to_index(key);
+        assert!(idx < N, "invalid discriminant for key type");
         &mut self.storage[idx]
     }
 
-    /// Takes `self` by value and returns an iterator over all the values
-    /// stored in this map.
-    pub fn into_values(self) -> impl Iterator<Item = V> {
-        self.storage.into_iter()
+    /// Returns a reference to the value associated with the given key, or
+    /// `None` if the key's discriminant is out of range.
+    ///
+    /// This can only return `None` if `K` has a hand-written `PrimitiveEnum`
+    /// implementation that claims fewer variants than the enum has, where
+    /// `get` would read out of bounds. Every key of a correct implementation
+    /// has a value.
+    ///
+    /// This doesn't make invalid enum values safe to use: creating a value of
+    /// `K` with a discriminant that none of its variants has, for example
+    /// through a transmute or FFI, is undefined behavior in itself.
+    #[inline(always)]
+    pub fn try_get(&self, key: K) -> Option<&V> {
+        self.storage.get(key_to_index(key))
     }
 
-    /// Returns an iterator over shared references to all values stored in this
-    /// map in arbitrary order.
-    pub fn values(&self) -> impl Iterator<Item = &V> {
-        self.storage.iter()
+    /// Returns a mutable reference to the value associated with the given key,
+    /// or `None` if the key's discriminant is out of range.
+    ///
+    /// See `try_get` for when this can return `None`.
+    #[inline(always)]
+    pub fn try_get_mut(&mut self, key: K) -> Option<&mut V> {
+        self.storage.get_mut(key_to_index(key))
     }
 
-    /// Returns an iterator over mutable references to all values stored in
-    /// this map in arbitrary order.
-    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut V> {
-        self.storage.iter_mut()
+    /// Returns a reference to the value associated with the given key, or
+    /// `None` if the key's discriminant is out of range.
+    ///
+    /// This is the same as `try_get`.
+    #[inline(always)]
+    pub fn checked_get(&self, key: K) -> Option<&V> {
+        self.try_get(key)
     }
-}
 
-impl<K: Key, V, const N: usize> Index<K> for Assoc<K, V>
-where
-    K::Impl: KeyImpl<Storage<V> = [V; N]>,
-{
-    type Output = V;
+    /// Returns a reference to the value associated with the given key, or
+    /// `default` if the key's discriminant is out of range.
+    ///
+    /// See `try_get` for when `default` is returned.
+    #[inline(always)]
+    pub fn get_or<'a>(&'a self, key: K, default: &'a V) -> &'a V {
+        self.try_get(key).unwrap_or(default)
+    }
 
+    /// Returns the key whose discriminant is `n`, or `None` if `n` is not less
+    /// than the number of keys.
     #[inline(always)]
-    fn index(&self, index: K) -> &Self::Output {
-        self.get(index)
+    pub fn nth_key(&self, n: usize) -> Option<K> {
+        if n < N {
+            // SAFETY: `n` is less than `N`.
+            Some(unsafe { index_to_key(n) })
+        } else {
+            None
+        }
     }
-}
 
-impl<K: Key, V, const N: usize> IndexMut<K> for Assoc<K, V>
-where
-    K::Impl: KeyImpl<Storage<V> = [V; N]>,
-{
+    /// Returns a reference to the value associated with the key whose
+    /// discriminant is `n`, or `None` if `n` is not less than the number of
+    /// keys.
     #[inline(always)]
-    fn index_mut(&mut self, index: K) -> &mut Self::Output {
-        self.get_mut(index)
+    pub fn nth_value(&self, n: usize) -> Option<&V> {
+        self.storage.get(n)
     }
-}
 
-impl<K: Key, V, const N: usize> Assoc<K, MaybeUninit<V>>
-where
-    K::Impl: KeyImpl<Storage<V> = [V; N]>,
-    K::Impl: KeyImpl<Storage<MaybeUninit<V>> = [MaybeUninit<V>; N]>,
-{
-    pub const fn new_uninit() -> Self {
-        // SAFETY: we are transmute an uninitialized array to an array with
-        // uninitialized elements, which is always safe.
-        let storage: [MaybeUninit<V>; N] = unsafe { MaybeUninit::uninit().assume_init() };
-        Self { storage }
+    /// Returns a mutable reference to the value associated with the key whose
+    /// discriminant is `n`, or `None` if `n` is not less than the number of
+    /// keys.
+    #[inline(always)]
+    pub fn nth_value_mut(&mut self, n: usize) -> Option<&mut V> {
+        self.storage.get_mut(n)
     }
 
-    /// Interprets this map as fully initialized, meaning that all its values
-    /// have been given a concrete value.
+    /// Returns a reference to the value associated with the variant whose
+    /// discriminant is `repr`, or `None` if there is no such variant.
+    #[inline(always)]
+    pub fn get_by_repr<R>(&self, repr: R) -> Option<&V>
+    where
+        K: PrimitiveEnum<Layout = PrimitiveEnumLayout<R, N>>,
+        R: ConstIntoUSize,
+    {
+        self.storage.get(into_usize(repr))
+    }
+
+    /// Returns a reference to the value associated with the variant whose
+    /// discriminant is `repr`, without checking that such a variant exists.
     ///
     /// # Safety
-    /// The caller must ensure that the map has actually been fully
-    /// initialized, meaning that all values have been given an initialized
-    /// value such that calling `MaybeUninit::assume_init` on them would be
-    /// safe.
-    pub const unsafe fn assume_init(self) -> Assoc<K, V> {
-        Assoc {
-            // SAFETY: the caller guarantees that all elements of
-            // `self.storage` have been initialized.
-            storage: unsafe { assume_init_array(self.storage) },
-        }
+    /// `repr` must be the discriminant of a variant of `K`, i.e. `get_by_repr`
+    /// must return `Some` for it.
+    #[inline(always)]
+    pub unsafe fn get_by_repr_unchecked<R>(&self, repr: R) -> &V
+    where
+        K: PrimitiveEnum<Layout = PrimitiveEnumLayout<R, N>>,
+        R: ConstIntoUSize,
+    {
+        // SAFETY: Upheld by the caller.
+        unsafe { self.storage.get_unchecked(into_usize(repr)) }
     }
-}
 
-/// Describes a key type for [Assoc].
-///
-/// # Safety
-/// Whenever `Storage<V>` is an array `[V; N]` for some N, `Self` must be less
-/// than `N` when converted to `usize` via `key_impl_to_index`.
-unsafe trait KeyImpl: Copy + TransmuteSafe<Self::Repr> {
-    type Storage<V>;
-    type Repr: Copy + ConstIntoUSize;
-}
+    /// Returns a mutable reference to the value associated with the given key.
+    ///
+    /// Since every key always has a value, this is equivalent to `get_mut`. It
+    /// exists for code written against map APIs that expect an `entry` method.
+    #[inline(always)]
+    pub fn entry(&mut self, key: K) -> &mut V {
+        self.get_mut(key)
+    }
 
-#[doc(hidden)]
-#[inline(always)]
-pub const fn key_to_index<K: Key>(key: K) -> usize {
-    let key_impl = transmute_safe(key);
-    key_impl_to_index(key_impl)
-}
+    /// Applies `f` to the value associated with the given key in place.
+    #[inline(always)]
+    pub fn update(&mut self, key: K, f: impl FnOnce(&mut V)) {
+        f(self.get_mut(key));
+    }
 
-#[inline(always)]
-const fn key_impl_to_index<K: KeyImpl>(key: K) -> usize {
-    let repr: K::Repr = transmute_safe(key);
-    into_usize(repr)
-}
+    /// Replaces the value associated with the given key with `V::DEFAULT`,
+    /// returning the old value.
+    #[inline(always)]
+    pub fn take(&mut self, key: K) -> V
+    where
+        V: ConstDefault,
+    {
+        mem::replace(self.get_mut(key), V::DEFAULT)
+    }
 
-/// Indirectly defines a way to use `Self` as a key for [Assoc].
-trait Key: TransmuteSafe<Self::Impl> {
-    type Impl: KeyImpl;
-}
+    /// Associates `value` with the given key and returns `self`, allowing
+    /// calls to be chained.
+    #[inline(always)]
+    pub fn set(&mut self, key: K, value: V) -> &mut Self {
+        *self.get_mut(key) = value;
+        self
+    }
 
-#[repr(transparent)]
-#[derive_where(Copy, Clone)]
-struct EnumKeyImpl<T: PrimitiveEnum, _U>(T, PhantomData<_U>);
+    /// Takes `self` by value, associates `value` with the given key and
+    /// returns the updated map.
+    #[inline(always)]
+    pub fn with(mut self, key: K, value: V) -> Self {
+        self.set(key, value);
+        self
+    }
 
-// SAFETY: `EnumKey<T>` has the same representation as `T`.
-unsafe impl<T: PrimitiveEnum, _U> TransmuteSafe<EnumKeyImpl<T, _U>> for T {}
+    /// Returns references to the values associated with two keys, which may be
+    /// the same.
+    #[inline(always)]
+    pub fn get2(&self, a: K, b: K) -> (&V, &V) {
+        (self.get(a), self.get(b))
+    }
 
-// SAFETY: `EnumKey<T>` has the same representation as `T`, while `T` has the
-// same representation as `<T::Layout as EnumLayoutTrait>::Discriminant`, since
-// `PrimitiveEnum` implies `TransmuteSafe<<T::Layout as EnumLayoutTrait>::Discriminant>`.
-unsafe impl<T: PrimitiveEnum, _U>
-    TransmuteSafe<<T::Layout as PrimitiveEnumLayoutTrait>::Discriminant> for EnumKeyImpl<T, _U>
-{
-}
+    /// Returns references to the values associated with each of the given
+    /// keys, which may repeat.
+    #[inline(always)]
+    pub fn get_many<const M: usize>(&self, keys: [K; M]) -> [&V; M] {
+        keys.map(|key| self.get(key))
+    }
 
-impl<T: PrimitiveEnum> Key for T
-where
-    EnumKeyImpl<T, <<T as PrimitiveEnum>::Layout as PrimitiveEnumLayoutTrait>::MaxVariants>:
-        KeyImpl,
-{
-    type Impl = EnumKeyImpl<T, <T::Layout as PrimitiveEnumLayoutTrait>::MaxVariants>;
-}
+    /// Returns mutable references to the values associated with each of the
+    /// given keys, without checking that the keys are distinct.
+    ///
+    /// # Safety
+    /// The caller must ensure that no two keys in `keys` are equal, that is,
+    /// that all of them map to distinct values. Otherwise, the returned
+    /// mutable references would alias.
+    #[inline(always)]
+    pub unsafe fn get_many_unchecked_mut<const M: usize>(&mut self, keys: [K; M]) -> [&mut V; M] {
+        let ptr = self.storage.as_mut_ptr();
 
-/// Indicates that `Self` is a primitive enum type, meaning that it is an enum
-/// with a `#[repr(primitive_type)]` attribute.
-///
-/// # Safety
-/// The implementors must ensure that `Layout` exactly describes `Self`.
-pub unsafe trait PrimitiveEnum: Copy {
-    /// The layout of `Self`.
-    type Layout: PrimitiveEnumLayoutTrait;
-}
+        keys.map(|key| {
+            let idx = key_to_index(key);
+            // SAFETY: The invariant of `KeyImpl` guarantees that `idx` is
+            // always less than `self.storage.len()`, and the caller
+            // guarantees that no two keys produce the same `idx`.
+            unsafe { &mut *ptr.add(idx) }
+        })
+    }
 
-// SAFETY: The invariant of `PrimitiveEnum` implies that `Self` always
-// represents a valid enum discriminant when converted to usize, so it must be
-// a non-negative integer that is less than `MAX_VARIANTS`.
-unsafe impl<T: PrimitiveEnum, const MAX_VARIANTS: usize> KeyImpl
+    /// Returns mutable references to the values associated with two distinct
+    /// keys.
+    ///
+    /// # Panics
+    /// Panics if `a` and `b` are the same key.
+    pub fn get_pair_mut(&mut self, a: K, b: K) -> (&mut V, &mut V) {
+        let (a, b) = (key_to_index(a), key_to_index(b));
+        assert_ne!(a, b, "`get_pair_mut` requires two distinct keys");
+
+        if a < b {
+            let (left, right) = self.storage.split_at_mut(b);
+            (&mut left[a], &mut right[0])
+        } else {
+            let (left, right) = self.storage.split_at_mut(a);
+            (&mut right[0], &mut left[b])
+        }
+    }
+
+    /// Returns a copy of all the values stored in this map, ordered by key
+    /// discriminant.
+    ///
+    /// Unlike `values`, this can be used in const contexts.
+    #[inline(always)]
+    pub const fn values_array(&self) -> [V; N]
+    where
+        V: Copy,
+    {
+        self.storage
+    }
+
+    /// Takes `self` by value and returns its values as an array of length `M`,
+    /// ordered by key discriminant, or returns `self` unchanged if `M` is not
+    /// the number of keys.
+    pub fn try_into_array<const M: usize>(self) -> Result<[V; M], Self> {
+        if M != N {
+            return Err(self);
+        }
+
+        let mut values = self.storage.into_iter();
+
+        Ok(core::array::from_fn(|_| {
+            // SAFETY: `values` has `N` elements, `M == N` and `from_fn` calls
+            // this closure exactly `M` times.
+            unsafe { values.next().unwrap_unchecked() }
+        }))
+    }
+
+    /// Returns a slice of all the values stored in this map, ordered by key
+    /// discriminant.
+    ///
+    /// Unlike `values`, this can be used in const contexts.
+    #[inline(always)]
+    pub const fn const_values(&self) -> &[V] {
+        &self.storage
+    }
+
+    /// Replaces every value with a clone of the element of `values` at the
+    /// same position in discriminant order.
+    ///
+    /// # Panics
+    /// Panics if `values` doesn't have exactly `N` elements.
+    pub fn fill_from_slice(&mut self, values: &[V])
+    where
+        V: Clone,
+    {
+        if let Err(err) = self.try_fill_from_slice(values) {
+            panic!("{err}");
+        }
+    }
+
+    /// Replaces every value with a clone of the element of `values` at the
+    /// same position in discriminant order, or returns an error without
+    /// modifying the map if `values` doesn't have exactly `N` elements.
+    pub fn try_fill_from_slice(&mut self, values: &[V]) -> Result<(), LengthMismatchError>
+    where
+        V: Clone,
+    {
+        if values.len() != N {
+            return Err(LengthMismatchError {
+                expected: N,
+                actual: values.len(),
+            });
+        }
+
+        self.storage.clone_from_slice(values);
+        Ok(())
+    }
+
+    /// Swaps the value associated with the given key in this map with the
+    /// value associated with the same key in `other`.
+    #[inline(always)]
+    pub fn swap_with(&mut self, other: &mut Self, key: K) {
+        mem::swap(self.get_mut(key), other.get_mut(key));
+    }
+
+    /// Replaces each value with the result of applying `f` to it, in place.
+    ///
+    /// Each value is temporarily replaced with `V::DEFAULT` while `f` runs,
+    /// so if `f` panics, the value it was called with is left as `V::DEFAULT`.
+    pub fn replace_all_with(&mut self, mut f: impl FnMut(V) -> V)
+    where
+        V: ConstDefault,
+    {
+        for value in &mut self.storage {
+            *value = f(mem::replace(value, V::DEFAULT));
+        }
+    }
+
+    /// Moves each value to the key whose discriminant is `n` lower, wrapping
+    /// around to the highest keys.
+    pub fn rotate_left(&mut self, n: usize) {
+        if N != 0 {
+            self.storage.rotate_left(n % N);
+        }
+    }
+
+    /// Moves each value to the key whose discriminant is `n` higher, wrapping
+    /// around to the lowest keys.
+    pub fn rotate_right(&mut self, n: usize) {
+        if N != 0 {
+            self.storage.rotate_right(n % N);
+        }
+    }
+
+    /// Takes `self` by value and moves each value to the key whose
+    /// discriminant is `by` higher, wrapping around to the lowest keys.
+    ///
+    /// For example, with keys `A`, `B` and `C`, shifting by 1 moves the value
+    /// of `A` to `B`, the value of `B` to `C` and the value of `C` to `A`.
+    pub fn shift_keys(mut self, by: usize) -> Self {
+        self.rotate_right(by);
+        self
+    }
+
+    /// Moves the value associated with each key `k` to the key `perm[k]`.
+    ///
+    /// # Panics
+    /// Panics if `perm` is not a permutation, i.e. if two keys are mapped to
+    /// the same key.
+    pub fn permute(&mut self, perm: &Assoc<K, K>)
+    where
+        K::Impl: KeyImpl<Storage<K> = [K; N]>,
+    {
+        let targets = perm.storage.map(key_to_index);
+        let mut visited = [false; N];
+
+        for &target in &targets {
+            assert!(!visited[target], "`perm` is not a permutation");
+            visited[target] = true;
+        }
+
+        visited = [false; N];
+
+        for start in 0..N {
+            if visited[start] {
+                continue;
+            }
+
+            visited[start] = true;
+            let mut next = targets[start];
+
+            // Walk the cycle containing `start`, swapping each value into place.
+            while next != start {
+                self.storage.swap(start, next);
+                visited[next] = true;
+                next = targets[next];
+            }
+        }
+    }
+
+    /// Resets the value associated with every key to `V::DEFAULT`.
+    pub fn clear(&mut self)
+    where
+        V: ConstDefault,
+    {
+        self.storage = [V::DEFAULT; N];
+    }
+
+    /// Resets the value associated with every key to `V::DEFAULT`.
+    ///
+    /// Unlike `clear`, this can be used in const contexts, but requires
+    /// `V: Copy` since the old values can't be dropped there.
+    pub const fn const_clear(&mut self)
+    where
+        V: Copy + ConstDefault,
+    {
+        self.storage = [V::DEFAULT; N];
+    }
+
+    /// Overwrites the value associated with each key for which `overrides`
+    /// holds `Some` with a clone of that value, leaving the other values
+    /// untouched.
+    pub fn apply(&mut self, overrides: &Assoc<K, Option<V>>)
+    where
+        V: Clone,
+        K::Impl: KeyImpl<Storage<Option<V>> = [Option<V>; N]>,
+    {
+        for (value, new_value) in self.storage.iter_mut().zip(&overrides.storage) {
+            if let Some(new_value) = new_value {
+                value.clone_from(new_value);
+            }
+        }
+    }
+
+    /// Takes `self` by value and returns an iterator over all the values
+    /// stored in this map, ordered by key discriminant.
+    pub fn into_values(self) -> IntoValues<V, N> {
+        IntoValues(self.storage.into_iter())
+    }
+
+    /// Takes `self` by value and returns an iterator over all the values
+    /// stored in this map, from the highest key discriminant to the lowest.
+    pub fn into_values_rev(self) -> Rev<IntoValues<V, N>> {
+        self.into_values().rev()
+    }
+
+    /// Takes `self` by value and returns an array of all keys paired with
+    /// their values, ordered by key discriminant.
+    pub fn into_entries(self) -> [(K, V); N] {
+        let mut idx = 0;
+
+        self.storage.map(|value| {
+            // SAFETY: `map` calls this closure exactly `N` times, so `idx` is
+            // always less than `N`.
+            let key = unsafe { index_to_key(idx) };
+            idx += 1;
+            (key, value)
+        })
+    }
+
+    /// Returns an iterator over all keys of this map, ordered by discriminant.
+    pub fn keys(&self) -> impl Iterator<Item = K> + '_ {
+        self.iter().map(|(key, _)| key)
+    }
+
+    /// Folds all keys into one value by repeatedly applying `f`, starting with
+    /// `init`, in ascending order of discriminant.
+    pub fn fold_keys<B>(&self, init: B, mut f: impl FnMut(B, K) -> B) -> B {
+        let mut acc = init;
+
+        for idx in 0..N {
+            // SAFETY: `idx` is less than `N`.
+            acc = f(acc, unsafe { index_to_key(idx) });
+        }
+
+        acc
+    }
+
+    /// Folds all keys paired with shared references to their values, ordered
+    /// by key discriminant, stopping at the first error returned by `f`.
+    pub fn try_fold<B, E>(
+        &self,
+        init: B,
+        mut f: impl FnMut(B, K, &V) -> Result<B, E>,
+    ) -> Result<B, E> {
+        self.iter()
+            .try_fold(init, |acc, (key, value)| f(acc, key, value))
+    }
+
+    /// Takes `self` by value, dropping all its values, and returns an
+    /// iterator over all keys, ordered by discriminant.
+    pub fn into_keys(self) -> impl Iterator<Item = K> {
+        drop(self);
+
+        (0..N).map(|idx| {
+            // SAFETY: `idx` is less than `N`.
+            unsafe { index_to_key(idx) }
+        })
+    }
+
+    /// Returns an iterator over shared references to all values stored in this
+    /// map, ordered by key discriminant.
+    pub fn values(&self) -> impl Iterator<Item = &V> {
+        self.storage.iter()
+    }
+
+    /// Returns an iterator over mutable references to all values stored in
+    /// this map, ordered by key discriminant.
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut V> {
+        self.storage.iter_mut()
+    }
+
+    /// Returns an iterator over shared references to all values paired with
+    /// their indices, ordered by key discriminant.
+    ///
+    /// This is cheaper than `indexed` when the keys themselves aren't needed.
+    pub fn iter_values_with_index(&self) -> impl Iterator<Item = (usize, &V)> {
+        self.storage.iter().enumerate()
+    }
+
+    /// Returns an iterator over mutable references to all values paired with
+    /// their indices, ordered by key discriminant.
+    pub fn iter_values_with_index_mut(&mut self) -> impl Iterator<Item = (usize, &mut V)> {
+        self.storage.iter_mut().enumerate()
+    }
+
+    /// Returns a map where each key is associated with the result of folding
+    /// the values of all keys up to and including it, in discriminant order.
+    ///
+    /// `f` is called with the previous accumulator, starting with `init`, and
+    /// the next value. If `f` panics, the accumulators it has produced so far
+    /// are leaked.
+    pub fn prefix_scan<B>(&self, init: B, mut f: impl FnMut(&B, &V) -> B) -> Assoc<K, B>
+    where
+        K::Impl: KeyImpl<Storage<B> = [B; N]>,
+    {
+        let mut values = [const { MaybeUninit::uninit() }; N];
+        let mut acc = &init;
+
+        for (slot, value) in values.iter_mut().zip(&self.storage) {
+            acc = slot.write(f(acc, value));
+        }
+
+        // SAFETY: Both arrays have `N` elements, so the loop above initializes
+        // every element of `values`.
+        Assoc::from_values(unsafe { assume_init_array(values) })
+    }
+
+    /// Returns the smallest value. If several values are equally small, the
+    /// one associated with the key with the lowest discriminant is returned.
+    ///
+    /// The map must not be empty, which is checked at compile time.
+    pub fn min_value(&self) -> &V
+    where
+        V: Ord,
+    {
+        const { assert!(N > 0, "`min_value` requires at least one key") };
+        // SAFETY: `self.storage` is not empty, since `N > 0`.
+        unsafe { self.storage.iter().min().unwrap_unchecked() }
+    }
+
+    /// Returns the largest value. If several values are equally large, the
+    /// one associated with the key with the highest discriminant is returned.
+    ///
+    /// The map must not be empty, which is checked at compile time.
+    pub fn max_value(&self) -> &V
+    where
+        V: Ord,
+    {
+        const { assert!(N > 0, "`max_value` requires at least one key") };
+        // SAFETY: `self.storage` is not empty, since `N > 0`.
+        unsafe { self.storage.iter().max().unwrap_unchecked() }
+    }
+
+    /// Returns a map from each possible value to the number of keys it is
+    /// associated with.
+    pub fn counts_by_value<const M: usize>(&self) -> Assoc<V, usize>
+    where
+        V: Key,
+        V::Impl: KeyImpl<Storage<usize> = [usize; M]>,
+    {
+        let mut counts = Assoc::<V, usize>::splat(0);
+
+        for &value in &self.storage {
+            counts[value] += 1;
+        }
+
+        counts
+    }
+
+    /// Returns the sum of all values.
+    pub fn sum<'a>(&'a self) -> V
+    where
+        V: Sum<&'a V>,
+    {
+        self.storage.iter().sum()
+    }
+
+    /// Returns the product of all values.
+    pub fn product<'a>(&'a self) -> V
+    where
+        V: Product<&'a V>,
+    {
+        self.storage.iter().product()
+    }
+
+    /// Returns the sum of all values.
+    ///
+    /// Unlike `sum`, this can be used in const contexts. Overflow is handled
+    /// the same way as for the `+` operator.
+    pub const fn const_sum(&self) -> V
+    where
+        V: ConstNum,
+    {
+        let mut sum = V::ZERO;
+        let mut idx = 0;
+
+        while idx < N {
+            sum = const_add(sum, self.storage[idx]);
+            idx += 1;
+        }
+
+        sum
+    }
+
+    /// Returns the product of all values.
+    ///
+    /// Unlike `product`, this can be used in const contexts. Overflow is
+    /// handled the same way as for the `*` operator.
+    pub const fn const_product(&self) -> V
+    where
+        V: ConstNum,
+    {
+        let mut product = V::ONE;
+        let mut idx = 0;
+
+        while idx < N {
+            product = const_mul(product, self.storage[idx]);
+            idx += 1;
+        }
+
+        product
+    }
+
+    /// Adds `amount` to the value associated with `key`, saturating at the
+    /// numeric bounds of `V`.
+    pub fn saturating_add(&mut self, key: K, amount: V)
+    where
+        V: SaturatingOps,
+    {
+        let value = self.get_mut(key);
+        *value = value.saturating_add(amount);
+    }
+
+    /// Subtracts `amount` from the value associated with `key`, saturating at
+    /// the numeric bounds of `V`.
+    pub fn saturating_sub(&mut self, key: K, amount: V)
+    where
+        V: SaturatingOps,
+    {
+        let value = self.get_mut(key);
+        *value = value.saturating_sub(amount);
+    }
+
+    /// Returns an iterator over slices of `chunk_size` values, ordered by key
+    /// discriminant. The last slice may be shorter.
+    ///
+    /// # Panics
+    /// Panics if `chunk_size` is 0.
+    pub fn values_chunks(&self, chunk_size: usize) -> impl Iterator<Item = &[V]> {
+        self.storage.chunks(chunk_size)
+    }
+
+    /// Returns an iterator over mutable slices of `chunk_size` values, ordered
+    /// by key discriminant. The last slice may be shorter.
+    ///
+    /// # Panics
+    /// Panics if `chunk_size` is 0.
+    pub fn values_chunks_mut(&mut self, chunk_size: usize) -> impl Iterator<Item = &mut [V]> {
+        self.storage.chunks_mut(chunk_size)
+    }
+
+    /// Returns an iterator over pairs of values associated with adjacent keys,
+    /// ordered by key discriminant.
+    pub fn value_windows(&self) -> impl Iterator<Item = (&V, &V)> {
+        self.storage
+            .windows(2)
+            .map(|window| (&window[0], &window[1]))
+    }
+
+    /// Returns the key with the lowest discriminant whose value is equal to
+    /// `needle`, or `None` if there is no such key.
+    pub fn position_of(&self, needle: &V) -> Option<K>
+    where
+        V: PartialEq,
+    {
+        let idx = self.storage.iter().position(|value| value == needle)?;
+        // SAFETY: `idx` is less than `N`, since it is an index into `self.storage`.
+        Some(unsafe { index_to_key(idx) })
+    }
+
+    /// Returns the key with the lowest discriminant whose value satisfies
+    /// `pred`, or `None` if there is no such key.
+    pub fn key_of_first(&self, pred: impl FnMut(&V) -> bool) -> Option<K> {
+        let idx = self.storage.iter().position(pred)?;
+        // SAFETY: `idx` is less than `N`, since it is an index into `self.storage`.
+        Some(unsafe { index_to_key(idx) })
+    }
+
+    /// Returns an iterator over all keys whose values satisfy `pred`, ordered
+    /// by discriminant.
+    pub fn all_keys_matching<'a>(
+        &'a self,
+        mut pred: impl FnMut(&V) -> bool + 'a,
+    ) -> impl Iterator<Item = K> + 'a {
+        self.iter()
+            .filter_map(move |(key, value)| pred(value).then_some(key))
+    }
+
+    /// Takes `self` by value and applies `f` to each value, producing a map
+    /// with the same keys.
+    pub fn map_values<U>(self, f: impl FnMut(V) -> U) -> Assoc<K, U>
+    where
+        K::Impl: KeyImpl<Storage<U> = [U; N]>,
+    {
+        Assoc::from_values(self.storage.map(f))
+    }
+
+    /// Takes `self` by value, dropping all its values, and returns a map with
+    /// the same keys where `new_values[i]` is associated with the key whose
+    /// discriminant is `i`.
+    pub fn with_values<U>(self, new_values: [U; N]) -> Assoc<K, U>
+    where
+        K::Impl: KeyImpl<Storage<U> = [U; N]>,
+    {
+        Assoc::from_values(new_values)
+    }
+
+    /// Takes `self` by value and applies `f` to the index and value of each
+    /// entry, in discriminant order, producing a map with the same keys.
+    pub fn map_values_indexed<U>(self, mut f: impl FnMut(usize, V) -> U) -> Assoc<K, U>
+    where
+        K::Impl: KeyImpl<Storage<U> = [U; N]>,
+    {
+        let mut idx = 0;
+
+        Assoc::from_values(self.storage.map(|value| {
+            let mapped = f(idx, value);
+            idx += 1;
+            mapped
+        }))
+    }
+
+    /// Takes `self` by value and folds all values into one by repeatedly
+    /// applying `f`, in discriminant order.
+    ///
+    /// Returns `None` only if the map is empty.
+    pub fn reduce(self, f: impl FnMut(V, V) -> V) -> Option<V> {
+        self.storage.into_iter().reduce(f)
+    }
+
+    /// Takes `self` by value and applies `f` to each value, producing a map
+    /// with the same keys where values for which `f` returned `None` are
+    /// `None`.
+    pub fn filter_map_values<U>(self, f: impl FnMut(V) -> Option<U>) -> Assoc<K, Option<U>>
+    where
+        K::Impl: KeyImpl<Storage<Option<U>> = [Option<U>; N]>,
+    {
+        self.map_values(f)
+    }
+
+    /// Takes `self` by value and returns a map where the keys satisfying
+    /// `keep` are associated with `Some` of their values, and all other keys
+    /// are associated with `None`, dropping their values.
+    pub fn mask(self, mut keep: impl FnMut(K) -> bool) -> Assoc<K, Option<V>>
+    where
+        K::Impl: KeyImpl<Storage<Option<V>> = [Option<V>; N]>,
+    {
+        let mut idx = 0;
+
+        Assoc::from_values(self.storage.map(|value| {
+            // SAFETY: `map` calls this closure exactly `N` times, so `idx` is
+            // always less than `N`.
+            let key = unsafe { index_to_key(idx) };
+            idx += 1;
+            keep(key).then_some(value)
+        }))
+    }
+
+    /// Applies `f` to a shared reference to each value, producing a map with
+    /// the same keys while leaving `self` intact.
+    pub fn map_ref<U>(&self, f: impl FnMut(&V) -> U) -> Assoc<K, U>
+    where
+        K::Impl: KeyImpl<Storage<U> = [U; N]>,
+    {
+        Assoc::from_values(self.storage.each_ref().map(f))
+    }
+
+    /// Takes `self` by value and moves each value to the key `f` maps its
+    /// current key to. Keys of `K2` that no key maps to hold `V::DEFAULT`.
+    ///
+    /// # Panics
+    /// Panics if `f` maps two different keys to the same key, since one of
+    /// the values would be lost otherwise.
+    pub fn map_keys<K2: Key, const M: usize>(self, mut f: impl FnMut(K) -> K2) -> Assoc<K2, V>
+    where
+        V: ConstDefault,
+        K2::Impl: KeyImpl<Storage<V> = [V; M]>,
+    {
+        let mut mapped = Assoc::<K2, V>::from_fn(|_| V::DEFAULT);
+        let mut occupied = [false; M];
+
+        for (idx, value) in self.storage.into_iter().enumerate() {
+            // SAFETY: `idx` is less than `N`, since it is an index into `self.storage`.
+            let new_key = f(unsafe { index_to_key(idx) });
+            let new_idx = key_to_index(new_key);
+
+            assert!(
+                !mem::replace(&mut occupied[new_idx], true),
+                "`map_keys` requires `f` to map distinct keys to distinct keys"
+            );
+
+            mapped.storage[new_idx] = value;
+        }
+
+        mapped
+    }
+
+    /// Returns a map where the given keys are associated with references to
+    /// their values and all other keys are associated with `None`.
+    pub fn select<'a>(&'a self, keys: &[K]) -> Assoc<K, Option<&'a V>>
+    where
+        K::Impl: KeyImpl<Storage<Option<&'a V>> = [Option<&'a V>; N]>,
+    {
+        let mut selected = Assoc::<K, Option<&'a V>>::splat(None);
+
+        for &key in keys {
+            *selected.get_mut(key) = Some(self.get(key));
+        }
+
+        selected
+    }
+
+    /// Returns an iterator over the given keys paired with references to their
+    /// values, in the order of `keys`.
+    ///
+    /// Unlike `select`, this doesn't build a map.
+    pub fn iter_selected<'a>(&'a self, keys: &'a [K]) -> impl Iterator<Item = (K, &'a V)> {
+        keys.iter().map(|&key| (key, self.get(key)))
+    }
+
+    /// Returns `true` if the values associated with each key are equal in
+    /// `self` and `other`, except for the keys in `ignore`.
+    pub fn eq_ignoring(&self, other: &Self, ignore: &[K]) -> bool
+    where
+        V: PartialEq,
+        K::Impl: KeyImpl<Storage<bool> = [bool; N]>,
+    {
+        let mut ignored = Assoc::<K, bool>::splat(false);
+
+        for &key in ignore {
+            ignored[key] = true;
+        }
+
+        (0..N).all(|idx| ignored.storage[idx] || self.storage[idx] == other.storage[idx])
+    }
+
+    /// Splits `self` into two maps: the first holds the values of keys with a
+    /// discriminant lower than that of `boundary`, and the second holds the
+    /// values of all other keys. Keys belonging to the other map are
+    /// associated with `None`.
+    pub fn split_at(self, boundary: K) -> (Assoc<K, Option<V>>, Assoc<K, Option<V>>)
+    where
+        K::Impl: KeyImpl<Storage<Option<V>> = [Option<V>; N]>,
+    {
+        let boundary = key_to_index(boundary);
+        let mut left = Assoc::<K, Option<V>>::from_fn(|_| None);
+        let mut right = Assoc::from_fn(|_| None);
+
+        for (idx, value) in self.storage.into_iter().enumerate() {
+            let half = if idx < boundary {
+                &mut left
+            } else {
+                &mut right
+            };
+            half.storage[idx] = Some(value);
+        }
+
+        (left, right)
+    }
+
+    /// Takes `self` by value and moves the values associated with the `M`
+    /// keys with the lowest discriminants to a map keyed by `K2`, dropping
+    /// the rest.
+    ///
+    /// `K2` must not have more keys than `K`, which is checked at compile time.
+    pub fn truncate_to<K2: Key, const M: usize>(self) -> Assoc<K2, V>
+    where
+        K2::Impl: KeyImpl<Storage<V> = [V; M]>,
+    {
+        const {
+            assert!(
+                M <= N,
+                "`truncate_to` requires `K2` to have at most as many keys as `K`"
+            )
+        };
+
+        let mut values = self.storage.into_iter();
+
+        Assoc::from_values(core::array::from_fn(|_| {
+            // SAFETY: `values` has `N` elements, `M <= N` and `from_fn` calls
+            // this closure exactly `M` times.
+            unsafe { values.next().unwrap_unchecked() }
+        }))
+    }
+
+    /// Takes `self` and `other` by value and pairs up the values associated
+    /// with the same key.
+    pub fn zip<U>(self, other: Assoc<K, U>) -> Assoc<K, (V, U)>
+    where
+        K::Impl: KeyImpl<Storage<U> = [U; N]>,
+        K::Impl: KeyImpl<Storage<(V, U)> = [(V, U); N]>,
+    {
+        self.zip_with(other, |value, other_value| (value, other_value))
+    }
+
+    /// Takes `self` and `other` by value and combines the values associated
+    /// with the same key using `f`.
+    ///
+    /// `f` is called in ascending order of key discriminant.
+    pub fn zip_with<U, W>(self, other: Assoc<K, U>, mut f: impl FnMut(V, U) -> W) -> Assoc<K, W>
+    where
+        K::Impl: KeyImpl<Storage<U> = [U; N]>,
+        K::Impl: KeyImpl<Storage<W> = [W; N]>,
+    {
+        let mut other = other.storage.into_iter();
+
+        Assoc::from_values(self.storage.map(|value| {
+            // SAFETY: Both arrays have `N` elements and `map` calls this
+            // closure exactly `N` times.
+            let other_value = unsafe { other.next().unwrap_unchecked() };
+            f(value, other_value)
+        }))
+    }
+
+    /// Returns an iterator over all keys paired with shared references to
+    /// their values, ordered by key discriminant.
+    pub fn iter(&self) -> impl Iterator<Item = (K, &V)> {
+        self.indexed().map(|(_, key, value)| (key, value))
+    }
+
+    /// Returns an iterator over all keys paired with mutable references to
+    /// their values, ordered by key discriminant.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (K, &mut V)> {
+        self.storage.iter_mut().enumerate().map(|(idx, value)| {
+            // SAFETY: `idx` is less than `N`, since it is an index into `self.storage`.
+            let key = unsafe { index_to_key(idx) };
+            (key, value)
+        })
+    }
+
+    /// Returns an iterator over the storage index, the key and a shared
+    /// reference to the value of every entry, ordered by key discriminant.
+    pub fn indexed(&self) -> impl Iterator<Item = (usize, K, &V)> {
+        self.storage.iter().enumerate().map(|(idx, value)| {
+            // SAFETY: `idx` is less than `N`, since it is an index into `self.storage`.
+            let key = unsafe { index_to_key(idx) };
+            (idx, key, value)
+        })
+    }
+
+    /// Formats the map like its `Debug` implementation, but renders each key
+    /// as the name returned by `key_name`.
+    ///
+    /// This is meant to be called from custom `Debug` implementations.
+    pub fn fmt_into(
+        &self,
+        f: &mut Formatter<'_>,
+        key_name: impl Fn(K) -> &'static str,
+    ) -> fmt::Result
+    where
+        V: Debug,
+    {
+        let mut map = f.debug_map();
+
+        for (key, value) in self.iter() {
+            map.entry(&format_args!("{}", key_name(key)), value);
+        }
+
+        map.finish()
+    }
+
+    /// Returns a wrapper whose `Debug` implementation only includes the
+    /// entries whose values differ from `V::DEFAULT`, followed by `..` if any
+    /// entries were left out.
+    pub fn debug_non_default(&self) -> DebugNonDefault<'_, K, V>
+    where
+        V: ConstDefault,
+    {
+        DebugNonDefault(self)
+    }
+}
+
+impl<K: Key, A, B, const N: usize> Assoc<K, (A, B)>
+where
+    K::Impl: KeyImpl<Storage<(A, B)> = [(A, B); N]>,
+{
+    /// Takes `self` by value and splits each pair of values, producing two
+    /// maps with the same keys. This is the inverse of `zip`.
+    pub fn unzip(self) -> (Assoc<K, A>, Assoc<K, B>)
+    where
+        K::Impl: KeyImpl<Storage<A> = [A; N]>,
+        K::Impl: KeyImpl<Storage<B> = [B; N]>,
+    {
+        let mut left = [const { MaybeUninit::uninit() }; N];
+        let mut right = [const { MaybeUninit::uninit() }; N];
+
+        for (idx, (a, b)) in self.storage.into_iter().enumerate() {
+            left[idx].write(a);
+            right[idx].write(b);
+        }
+
+        // SAFETY: The loop above runs exactly `N` times, initializing every
+        // element of both arrays.
+        let (left, right) = unsafe { (assume_init_array(left), assume_init_array(right)) };
+
+        (Assoc::from_values(left), Assoc::from_values(right))
+    }
+}
+
+impl<K: Key, V, const N: usize> Assoc<K, Option<V>>
+where
+    K::Impl: KeyImpl<Storage<Option<V>> = [Option<V>; N]>,
+{
+    /// Creates a map where the keys yielded by `iter` are associated with
+    /// `Some` of their values, and all other keys are associated with `None`.
+    ///
+    /// If a key is yielded more than once, the last value is kept.
+    pub fn from_partial_iter(iter: impl IntoIterator<Item = (K, V)>) -> Self {
+        let mut map = Self::from_fn(|_| None);
+
+        for (key, value) in iter {
+            *map.get_mut(key) = Some(value);
+        }
+
+        map
+    }
+}
+
+impl<K: Key, T, E, const N: usize> Assoc<K, Result<T, E>>
+where
+    K::Impl: KeyImpl<Storage<Result<T, E>> = [Result<T, E>; N]>,
+{
+    /// Takes `self` by value and applies `f` to each `Err` value, leaving
+    /// `Ok` values untouched and producing a map with the same keys.
+    pub fn map_err_values<F>(self, mut f: impl FnMut(E) -> F) -> Assoc<K, Result<T, F>>
+    where
+        K::Impl: KeyImpl<Storage<Result<T, F>> = [Result<T, F>; N]>,
+    {
+        Assoc::from_values(self.storage.map(|value| value.map_err(&mut f)))
+    }
+
+    /// Takes `self` by value and returns a map of all `Ok` values, or the
+    /// error with the lowest key discriminant if there is one.
+    ///
+    /// On error, all `Ok` values are dropped.
+    pub fn collect_results(self) -> Result<Assoc<K, T>, E>
+    where
+        K::Impl: KeyImpl<Storage<T> = [T; N]>,
+    {
+        // SAFETY: `self.storage` yields exactly `N` items.
+        let values = unsafe { try_collect_array(self.storage.into_iter())? };
+        Ok(Assoc::from_values(values))
+    }
+}
+
+impl<K: Key + Debug, V: Debug, const N: usize> Debug for Assoc<K, V>
+where
+    K::Impl: KeyImpl<Storage<V> = [V; N]>,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+/// Formats an [`Assoc`] like its `Debug` implementation, but only includes the
+/// entries whose values differ from `V::DEFAULT`.
+///
+/// Returned by [`Assoc::debug_non_default`].
+pub struct DebugNonDefault<'a, K: Key, V>(&'a Assoc<K, V>);
+
+impl<K: Key + Debug, V: Debug + PartialEq + ConstDefault, const N: usize> Debug
+    for DebugNonDefault<'_, K, V>
+where
+    K::Impl: KeyImpl<Storage<V> = [V; N]>,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut map = f.debug_map();
+        let mut elided = false;
+
+        for (key, value) in self.0.iter() {
+            if *value == V::DEFAULT {
+                elided = true;
+            } else {
+                map.entry(&key, value);
+            }
+        }
+
+        if elided {
+            map.finish_non_exhaustive()
+        } else {
+            map.finish()
+        }
+    }
+}
+
+impl<K: Key, V, const N: usize> Index<K> for Assoc<K, V>
+where
+    K::Impl: KeyImpl<Storage<V> = [V; N]>,
+{
+    type Output = V;
+
+    #[inline(always)]
+    fn index(&self, index: K) -> &Self::Output {
+        self.get(index)
+    }
+}
+
+impl<K: Key, V, const N: usize> IndexMut<K> for Assoc<K, V>
+where
+    K::Impl: KeyImpl<Storage<V> = [V; N]>,
+{
+    #[inline(always)]
+    fn index_mut(&mut self, index: K) -> &mut Self::Output {
+        self.get_mut(index)
+    }
+}
+
+/// Builds a map from a slice of key-value pairs, cloning each value.
+///
+/// Every key must appear in `pairs` exactly once.
+impl<K: Key, V: Clone + ConstDefault, const N: usize> TryFrom<&[(K, V)]> for Assoc<K, V>
+where
+    K::Impl: KeyImpl<Storage<V> = [V; N]>,
+    K::Impl: KeyImpl<Storage<bool> = [bool; N]>,
+{
+    type Error = FromPairsError<K>;
+
+    fn try_from(pairs: &[(K, V)]) -> Result<Self, Self::Error> {
+        let mut map = Self::from_fn(|_| V::DEFAULT);
+        let mut missing = Assoc::<K, bool>::splat(true);
+
+        for (key, value) in pairs {
+            if !mem::replace(missing.get_mut(*key), false) {
+                return Err(FromPairsError::DuplicateKey(*key));
+            }
+
+            map.get_mut(*key).clone_from(value);
+        }
+
+        if missing.values().any(|&is_missing| is_missing) {
+            return Err(FromPairsError::MissingKeys(missing));
+        }
+
+        Ok(map)
+    }
+}
+
+/// An error returned when a sequence of values doesn't have exactly one value
+/// per key.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct LengthMismatchError {
+    /// The number of keys, and therefore the expected number of values.
+    pub expected: usize,
+    /// The number of values that were actually provided.
+    pub actual: usize,
+}
+
+impl Display for LengthMismatchError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "expected {} values, found {}",
+            self.expected, self.actual
+        )
+    }
+}
+
+/// An error returned when a sequence of key-value pairs doesn't have exactly
+/// one pair per key.
+pub enum FromPairsError<K: Key> {
+    /// The given key appears in more than one pair.
+    DuplicateKey(K),
+    /// Some keys don't appear in any pair. Missing keys are associated with
+    /// `true`.
+    MissingKeys(Assoc<K, bool>),
+}
+
+impl<K: Key + Debug, const N: usize> Debug for FromPairsError<K>
+where
+    K::Impl: KeyImpl<Storage<bool> = [bool; N]>,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DuplicateKey(key) => f.debug_tuple("DuplicateKey").field(key).finish(),
+            Self::MissingKeys(missing) => f.debug_tuple("MissingKeys").field(missing).finish(),
+        }
+    }
+}
+
+impl<K: Key + Debug, const N: usize> Display for FromPairsError<K>
+where
+    K::Impl: KeyImpl<Storage<bool> = [bool; N]>,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DuplicateKey(key) => write!(f, "duplicate key {key:?}"),
+            Self::MissingKeys(missing) => {
+                let keys = missing
+                    .iter()
+                    .filter(|(_, &is_missing)| is_missing)
+                    .map(|(key, _)| key);
+                f.write_str("missing keys ")?;
+                f.debug_list().entries(keys).finish()
+            }
+        }
+    }
+}
+
+/// An error returned when a sequence of key-value pairs doesn't have exactly
+/// one pair per key, reporting every offending key.
+pub struct MapBuildError<K: Key> {
+    missing: Assoc<K, bool>,
+    duplicated: Assoc<K, bool>,
+}
+
+impl<K: Key, const N: usize> MapBuildError<K>
+where
+    K::Impl: KeyImpl<Storage<bool> = [bool; N]>,
+{
+    /// Returns an iterator over the keys that don't appear in any pair,
+    /// ordered by discriminant.
+    pub fn missing(&self) -> impl Iterator<Item = K> + '_ {
+        self.missing
+            .iter()
+            .filter(|(_, &flag)| flag)
+            .map(|(key, _)| key)
+    }
+
+    /// Returns an iterator over the keys that appear in more than one pair,
+    /// ordered by discriminant.
+    pub fn duplicated(&self) -> impl Iterator<Item = K> + '_ {
+        self.duplicated
+            .iter()
+            .filter(|(_, &flag)| flag)
+            .map(|(key, _)| key)
+    }
+}
+
+impl<K: Key + Debug, const N: usize> Debug for MapBuildError<K>
+where
+    K::Impl: KeyImpl<Storage<bool> = [bool; N]>,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MapBuildError")
+            .field("missing", &DebugIter(|| self.missing()))
+            .field("duplicated", &DebugIter(|| self.duplicated()))
+            .finish()
+    }
+}
+
+impl<K: Key + Debug, const N: usize> Display for MapBuildError<K>
+where
+    K::Impl: KeyImpl<Storage<bool> = [bool; N]>,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let has_missing = self.missing().next().is_some();
+
+        if has_missing {
+            write!(f, "missing keys {:?}", DebugIter(|| self.missing()))?;
+        }
+
+        if self.duplicated().next().is_some() {
+            if has_missing {
+                f.write_str(", ")?;
+            }
+
+            write!(f, "duplicated keys {:?}", DebugIter(|| self.duplicated()))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Formats the items of the iterator returned by a closure as a list.
+struct DebugIter<F>(F);
+
+impl<F: Fn() -> I, I: Iterator<Item: Debug>> Debug for DebugIter<F> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries((self.0)()).finish()
+    }
+}
+
+impl<K: Key, V, const N: usize> Assoc<K, MaybeUninit<V>>
+where
+    K::Impl: KeyImpl<Storage<V> = [V; N]>,
+    K::Impl: KeyImpl<Storage<MaybeUninit<V>> = [MaybeUninit<V>; N]>,
+{
+    pub const fn new_uninit() -> Self {
+        // SAFETY: we are transmute an uninitialized array to an array with
+        // uninitialized elements, which is always safe.
+        let storage: [MaybeUninit<V>; N] = unsafe { MaybeUninit::uninit().assume_init() };
+        Self { storage }
+    }
+
+    /// Interprets this map as fully initialized, meaning that all its values
+    /// have been given a concrete value.
+    ///
+    /// # Safety
+    /// The caller must ensure that the map has actually been fully
+    /// initialized, meaning that all values have been given an initialized
+    /// value such that calling `MaybeUninit::assume_init` on them would be
+    /// safe.
+    pub const unsafe fn assume_init(self) -> Assoc<K, V> {
+        Assoc {
+            // SAFETY: the caller guarantees that all elements of
+            // `self.storage` have been initialized.
+            storage: unsafe { assume_init_array(self.storage) },
+        }
+    }
+}
+
+/// Describes a key type for [Assoc].
+///
+/// # Safety
+/// Whenever `Storage<V>` is an array `[V; N]` for some N, `Self` must be less
+/// than `N` when converted to `usize` via `key_impl_to_index`. Conversely,
+/// every `usize` less than `N` must be the image of some valid `Self` under
+/// `key_impl_to_index`.
+unsafe trait KeyImpl: Copy + TransmuteSafe<Self::Repr> {
+    type Storage<V>;
+    type Repr: Copy + ConstIntoUSize;
+
+    /// Evaluated whenever a key is converted to or from an index, so that
+    /// implementations can reject invalid parameters at compile time.
+    const ASSERT_VALID: () = ();
+}
+
+#[doc(hidden)]
+#[inline(always)]
+pub const fn key_to_index<K: Key>(key: K) -> usize {
+    let key_impl = transmute_safe(key);
+    key_impl_to_index(key_impl)
+}
+
+#[inline(always)]
+const fn key_impl_to_index<K: KeyImpl>(key: K) -> usize {
+    let () = K::ASSERT_VALID;
+    let repr: K::Repr = transmute_safe(key);
+    into_usize(repr)
+}
+
+/// The inverse of `key_to_index`.
+///
+/// # Safety
+/// `idx` must be less than `N`, where `N` is the length of
+/// `<K::Impl as KeyImpl>::Storage<V>` for any `V`.
+#[inline(always)]
+const unsafe fn index_to_key<K: Key>(idx: usize) -> K {
+    let () = <K::Impl as KeyImpl>::ASSERT_VALID;
+    let repr: <K::Impl as KeyImpl>::Repr = from_usize(idx);
+    // SAFETY: The invariant of `KeyImpl` guarantees that any `idx` less than
+    // `N` is the image of a valid key, and `K` has the same representation as
+    // `K::Impl`, which in turn has the same representation as its `Repr`.
+    unsafe { transmute_unchecked(transmute_unchecked::<_, K::Impl>(repr)) }
+}
+
+/// Indirectly defines a way to use `Self` as a key for [Assoc].
+trait Key: TransmuteSafe<Self::Impl> {
+    type Impl: KeyImpl;
+}
+
+#[repr(transparent)]
+#[derive_where(Copy, Clone)]
+struct EnumKeyImpl<T: PrimitiveEnum, _U>(T, PhantomData<_U>);
+
+// SAFETY: `EnumKeyImpl<T, _U>` has the same representation as `T`.
+unsafe impl<T: PrimitiveEnum, _U> TransmuteSafe<EnumKeyImpl<T, _U>> for T {}
+
+// SAFETY: `EnumKeyImpl<T, _U>` has the same representation as `T`, while `T` has the
+// same representation as `<T::Layout as PrimitiveEnumLayoutTrait>::Discriminant`, since
+// `PrimitiveEnum` implies `TransmuteSafe<<T::Layout as PrimitiveEnumLayoutTrait>::Discriminant>`.
+unsafe impl<T: PrimitiveEnum, _U>
+    TransmuteSafe<<T::Layout as PrimitiveEnumLayoutTrait>::Discriminant> for EnumKeyImpl<T, _U>
+{
+}
+
+impl<T: PrimitiveEnum> Key for T
+where
+    EnumKeyImpl<T, <<T as PrimitiveEnum>::Layout as PrimitiveEnumLayoutTrait>::MaxVariants>:
+        KeyImpl,
+{
+    type Impl = EnumKeyImpl<T, <T::Layout as PrimitiveEnumLayoutTrait>::MaxVariants>;
+}
+
+/// Indicates that `Self` is a primitive enum type, meaning that it is a
+/// fieldless enum stored as its discriminant of a primitive integer type.
+///
+/// Only a `#[repr(primitive_type)]` attribute guarantees this. The derive's
+/// `unsafe_repr` option also accepts enums without one, where it relies on
+/// rustc's unspecified layout and asserts it at compile time.
+///
+/// # Safety
+/// The implementors must ensure that:
+/// * `Layout` exactly describes `Self`, meaning that `Self` has the same size
+///   as `Discriminant` and that every variant has a discriminant less than
+///   `MAX_VARIANTS`.
+/// * Every integer in `0..MAX_VARIANTS` is the discriminant of some variant of
+///   `Self`, that is, the discriminants are exactly `0..MAX_VARIANTS`.
+///
+/// The second requirement lets an [`Assoc`] turn each of its storage indices
+/// back into a key, which `keys`, `iter`, `from_fn` and the `Debug`
+/// implementation rely on. It was added after the first one, so hand-written
+/// implementations for enums with gaps between discriminants, which used to be
+/// sound, no longer are. Use [`validate`] to check a hand-written
+/// implementation.
+pub unsafe trait PrimitiveEnum: Copy {
+    /// The layout of `Self`.
+    type Layout: PrimitiveEnumLayoutTrait;
+
+    /// Fails to evaluate if some discriminant of `Self` is out of range.
+    ///
+    /// Evaluated whenever a key of type `Self` is converted to or from an
+    /// index. The derive uses it to check the real discriminants of the enum.
+    #[doc(hidden)]
+    const ASSERT_DISCRIMINANTS: () = ();
+}
+
+/// Constants derived from the [`Layout`](PrimitiveEnum::Layout) of a
+/// [`PrimitiveEnum`].
+///
+/// This trait is sealed and implemented for all [`PrimitiveEnum`] types, so
+/// its constants always agree with the layout.
+#[sealed]
+pub trait PrimitiveEnumExt: PrimitiveEnum {
+    /// The number of variants of `Self`, which is also the number of values
+    /// stored in an [`Assoc`] keyed by `Self`.
+    const VARIANTS: usize;
+
+    /// Whether the discriminants of `Self` are exactly `0..VARIANTS`.
+    ///
+    /// The safety contract of [`PrimitiveEnum`] currently requires this, so it
+    /// is always `true`. Generic code can still check it to statically pick
+    /// between unchecked and checked lookups should gaps ever be supported.
+    const IS_CONTIGUOUS: bool;
+}
+
+#[sealed]
+impl<T: PrimitiveEnum> PrimitiveEnumExt for T {
+    const VARIANTS: usize =
+        <<T::Layout as PrimitiveEnumLayoutTrait>::MaxVariants as IsConstUSize>::N;
+    const IS_CONTIGUOUS: bool = true;
+}
+
+/// Describes how a [`PrimitiveEnum`] implementation contradicts the variants
+/// of the enum it is implemented for.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum LayoutError {
+    /// A variant has a discriminant that is not less than `MAX_VARIANTS`.
+    DiscriminantOutOfRange { discriminant: usize },
+    /// Two of the given variants have the same discriminant.
+    DuplicateDiscriminant { discriminant: usize },
+    /// None of the given variants has this discriminant, even though it is
+    /// less than `MAX_VARIANTS`.
+    MissingDiscriminant { discriminant: usize },
+}
+
+impl Display for LayoutError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            LayoutError::DiscriminantOutOfRange { discriminant } => {
+                write!(f, "discriminant {discriminant} is out of range")
+            }
+            LayoutError::DuplicateDiscriminant { discriminant } => {
+                write!(f, "discriminant {discriminant} is used by several variants")
+            }
+            LayoutError::MissingDiscriminant { discriminant } => {
+                write!(f, "discriminant {discriminant} is not used by any variant")
+            }
+        }
+    }
+}
+
+/// Checks that the `PrimitiveEnum` implementation of `T` is consistent with
+/// `variants`, which must list every variant of `T`.
+///
+/// This is meant to be used in tests or const assertions to gain confidence
+/// in hand-written implementations. Mismatched sizes between `T` and its
+/// discriminant type are already rejected at compile time.
+///
+/// # Example
+/// ```
+/// use const_assoc::{validate, PrimitiveEnum, PrimitiveEnumLayout};
+///
+/// #[repr(u8)]
+/// #[derive(Copy, Clone)]
+/// enum Letter {
+///     A,
+///     B,
+/// }
+///
+/// unsafe impl PrimitiveEnum for Letter {
+///     type Layout = PrimitiveEnumLayout<u8, 2>;
+/// }
+///
+/// const _: () = assert!(validate(&[Letter::A, Letter::B]).is_ok());
+/// ```
+pub const fn validate<T: PrimitiveEnum + Key>(variants: &[T]) -> Result<(), LayoutError> {
+    let max_variants = <<T::Layout as PrimitiveEnumLayoutTrait>::MaxVariants as IsConstUSize>::N;
+
+    let mut i = 0;
+
+    while i < variants.len() {
+        let discriminant = key_to_index(variants[i]);
+
+        if discriminant >= max_variants {
+            return Err(LayoutError::DiscriminantOutOfRange { discriminant });
+        }
+
+        let mut j = i + 1;
+
+        while j < variants.len() {
+            if key_to_index(variants[j]) == discriminant {
+                return Err(LayoutError::DuplicateDiscriminant { discriminant });
+            }
+
+            j += 1;
+        }
+
+        i += 1;
+    }
+
+    // Since all the discriminants are in range and distinct, they cover
+    // `0..max_variants` if and only if there are exactly `max_variants` of
+    // them. Look for the first gap otherwise.
+    let mut discriminant = 0;
+
+    while discriminant < max_variants {
+        let mut i = 0;
+
+        while i < variants.len() && key_to_index(variants[i]) != discriminant {
+            i += 1;
+        }
+
+        if i == variants.len() {
+            return Err(LayoutError::MissingDiscriminant { discriminant });
+        }
+
+        discriminant += 1;
+    }
+
+    Ok(())
+}
+
+// SAFETY: The invariant of `PrimitiveEnum` implies that `Self` always
+// represents a valid enum discriminant when converted to usize, so it must be
+// a non-negative integer that is less than `MAX_VARIANTS`. It also guarantees
+// that every integer less than `MAX_VARIANTS` is a valid discriminant.
+unsafe impl<T: PrimitiveEnum, const MAX_VARIANTS: usize> KeyImpl
     for EnumKeyImpl<T, ConstUSize<MAX_VARIANTS>>
 where
     <<T as PrimitiveEnum>::Layout as PrimitiveEnumLayoutTrait>::MaxVariants:
@@ -366,6 +2060,8 @@ where
 {
     type Storage<V> = [V; MAX_VARIANTS];
     type Repr = <<T as PrimitiveEnum>::Layout as PrimitiveEnumLayoutTrait>::Discriminant;
+
+    const ASSERT_VALID: () = T::ASSERT_DISCRIMINANTS;
 }
 
 // See `PrimitiveEnumLayout`.
@@ -378,9 +2074,9 @@ trait PrimitiveEnumLayoutTrait {
 ///
 /// # Parameters
 /// * `Discriminant` - The underlying numerical type used to represent enum variants.
-/// * `MAX_MAX_VARIANTS` - The maximum number of variants this enum can have, equal to
-///   the greatest discriminant value among the enum's variants plus 1.
-pub struct PrimitiveEnumLayout<Discriminant, const MAX_MAX_VARIANTS: usize> {
+/// * `MAX_VARIANTS` - The number of variants of the enum, which must also be the
+///   greatest discriminant value among the enum's variants plus 1.
+pub struct PrimitiveEnumLayout<Discriminant, const MAX_VARIANTS: usize> {
     _marker: PhantomData<Discriminant>,
 }
 
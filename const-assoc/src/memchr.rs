@@ -0,0 +1,16 @@
+use crate::{index_to_key, Assoc, Key, KeyImpl};
+
+impl<K: Key, const N: usize> Assoc<K, u8>
+where
+    K::Impl: KeyImpl<Storage<u8> = [u8; N]>,
+{
+    /// Returns the key with the lowest discriminant whose value is equal to
+    /// `needle`, or `None` if there is no such key.
+    ///
+    /// This is equivalent to `position_of`, but uses a vectorized search.
+    pub fn position_of_byte(&self, needle: u8) -> Option<K> {
+        let idx = ::memchr::memchr(needle, &self.storage)?;
+        // SAFETY: `idx` is less than `N`, since it is an index into `self.storage`.
+        Some(unsafe { index_to_key(idx) })
+    }
+}
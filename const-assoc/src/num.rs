@@ -0,0 +1,103 @@
+use sealed::sealed;
+use typewit::HasTypeWitness;
+
+/// Primitive integer types that can be added and multiplied in const contexts.
+///
+/// This trait is sealed and implemented for all primitive integer types.
+#[sealed]
+pub trait ConstNum: Copy + HasTypeWitness<NumWitness<Self>> {
+    /// The additive identity.
+    const ZERO: Self;
+    /// The multiplicative identity.
+    const ONE: Self;
+}
+
+macro_rules! impl_const_num {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            #[sealed]
+            impl ConstNum for $ty {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+            }
+        )*
+    };
+}
+
+impl_const_num!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+typewit::simple_type_witness! {
+    #[doc(hidden)]
+    pub enum NumWitness {
+        U8 = u8,
+        U16 = u16,
+        U32 = u32,
+        U64 = u64,
+        U128 = u128,
+        USize = usize,
+        I8 = i8,
+        I16 = i16,
+        I32 = i32,
+        I64 = i64,
+        I128 = i128,
+        ISize = isize,
+    }
+}
+
+macro_rules! const_num_op {
+    ($name:ident, $op:tt) => {
+        #[inline(always)]
+        pub(crate) const fn $name<T: ConstNum>(lhs: T, rhs: T) -> T {
+            match T::WITNESS {
+                NumWitness::U8(te) => te.to_left(te.to_right(lhs) $op te.to_right(rhs)),
+                NumWitness::U16(te) => te.to_left(te.to_right(lhs) $op te.to_right(rhs)),
+                NumWitness::U32(te) => te.to_left(te.to_right(lhs) $op te.to_right(rhs)),
+                NumWitness::U64(te) => te.to_left(te.to_right(lhs) $op te.to_right(rhs)),
+                NumWitness::U128(te) => te.to_left(te.to_right(lhs) $op te.to_right(rhs)),
+                NumWitness::USize(te) => te.to_left(te.to_right(lhs) $op te.to_right(rhs)),
+                NumWitness::I8(te) => te.to_left(te.to_right(lhs) $op te.to_right(rhs)),
+                NumWitness::I16(te) => te.to_left(te.to_right(lhs) $op te.to_right(rhs)),
+                NumWitness::I32(te) => te.to_left(te.to_right(lhs) $op te.to_right(rhs)),
+                NumWitness::I64(te) => te.to_left(te.to_right(lhs) $op te.to_right(rhs)),
+                NumWitness::I128(te) => te.to_left(te.to_right(lhs) $op te.to_right(rhs)),
+                NumWitness::ISize(te) => te.to_left(te.to_right(lhs) $op te.to_right(rhs)),
+            }
+        }
+    };
+}
+
+const_num_op!(const_add, +);
+const_num_op!(const_mul, *);
+
+/// Primitive integer types that support saturating arithmetic.
+///
+/// This trait is sealed and implemented for all primitive integer types.
+#[sealed]
+pub trait SaturatingOps: Copy {
+    /// Computes `self + rhs`, saturating at the numeric bounds.
+    fn saturating_add(self, rhs: Self) -> Self;
+
+    /// Computes `self - rhs`, saturating at the numeric bounds.
+    fn saturating_sub(self, rhs: Self) -> Self;
+}
+
+macro_rules! impl_saturating_ops {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            #[sealed]
+            impl SaturatingOps for $ty {
+                #[inline(always)]
+                fn saturating_add(self, rhs: Self) -> Self {
+                    <$ty>::saturating_add(self, rhs)
+                }
+
+                #[inline(always)]
+                fn saturating_sub(self, rhs: Self) -> Self {
+                    <$ty>::saturating_sub(self, rhs)
+                }
+            }
+        )*
+    };
+}
+
+impl_saturating_ops!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
@@ -0,0 +1,28 @@
+//! [`proptest`] strategies for generating [`Assoc`] instances.
+
+use crate::{Assoc, Key, KeyImpl};
+use ::proptest::arbitrary::{any, Arbitrary};
+use ::proptest::array::uniform;
+use ::proptest::strategy::Strategy;
+use core::fmt::Debug;
+
+/// Returns a strategy that generates maps whose values are produced by
+/// `values`.
+pub fn assoc_of<K, S, const N: usize>(values: S) -> impl Strategy<Value = Assoc<K, S::Value>>
+where
+    K: Key + Debug,
+    S: Strategy,
+    K::Impl: KeyImpl<Storage<S::Value> = [S::Value; N]>,
+{
+    uniform::<S, N>(values).prop_map(Assoc::from_values)
+}
+
+/// Returns a strategy that generates maps with arbitrary values.
+pub fn any_assoc<K, V, const N: usize>() -> impl Strategy<Value = Assoc<K, V>>
+where
+    K: Key + Debug,
+    V: Arbitrary,
+    K::Impl: KeyImpl<Storage<V> = [V; N]>,
+{
+    assoc_of(any::<V>())
+}
@@ -0,0 +1,47 @@
+use crate::{index_to_key, Assoc, Key, KeyImpl};
+use ::rayon::array::IntoIter;
+use ::rayon::iter::{Enumerate, IndexedParallelIterator, IntoParallelIterator, Map};
+use ::rayon::prelude::*;
+
+impl<K: Key, V, const N: usize> Assoc<K, V>
+where
+    K::Impl: KeyImpl<Storage<V> = [V; N]>,
+{
+    /// Returns a parallel iterator over shared references to all values
+    /// stored in this map.
+    pub fn par_values(&self) -> impl IndexedParallelIterator<Item = &V>
+    where
+        V: Sync,
+    {
+        self.storage.par_iter()
+    }
+
+    /// Returns a parallel iterator over mutable references to all values
+    /// stored in this map.
+    pub fn par_values_mut(&mut self) -> impl IndexedParallelIterator<Item = &mut V>
+    where
+        V: Send,
+    {
+        self.storage.par_iter_mut()
+    }
+}
+
+/// Consumes the map, yielding all keys paired with their values in parallel.
+impl<K: Key + Send, V: Send, const N: usize> IntoParallelIterator for Assoc<K, V>
+where
+    K::Impl: KeyImpl<Storage<V> = [V; N]>,
+{
+    type Item = (K, V);
+    type Iter = Map<Enumerate<IntoIter<V, N>>, fn((usize, V)) -> (K, V)>;
+
+    fn into_par_iter(self) -> Self::Iter {
+        self.storage
+            .into_par_iter()
+            .enumerate()
+            .map(|(idx, value)| {
+                // SAFETY: `idx` is less than `N`, since it is an index into `self.storage`.
+                let key = unsafe { index_to_key(idx) };
+                (key, value)
+            })
+    }
+}
@@ -0,0 +1,66 @@
+use crate::{Assoc, Key, KeyImpl};
+use ::rkyv::bytecheck::CheckBytes;
+use ::rkyv::rancor::Fallible;
+use ::rkyv::{Archive, Deserialize, Place, Portable, Serialize};
+
+// SAFETY: `Assoc<K, V>` is a `#[repr(transparent)]` wrapper around `[V; N]`,
+// which is `Portable` whenever `V` is.
+unsafe impl<K: Key, V: Portable, const N: usize> Portable for Assoc<K, V> where
+    K::Impl: KeyImpl<Storage<V> = [V; N]>
+{
+}
+
+// SAFETY: `Assoc<K, V>` is a `#[repr(transparent)]` wrapper around `[V; N]`,
+// so it is valid exactly when the underlying array is.
+unsafe impl<C: Fallible + ?Sized, K: Key, V, const N: usize> CheckBytes<C> for Assoc<K, V>
+where
+    K::Impl: KeyImpl<Storage<V> = [V; N]>,
+    [V; N]: CheckBytes<C>,
+{
+    unsafe fn check_bytes(value: *const Self, context: &mut C) -> Result<(), C::Error> {
+        // SAFETY: The caller guarantees that `value` is aligned and points to
+        // enough initialized bytes for `Self`, and therefore for `[V; N]`.
+        unsafe { <[V; N]>::check_bytes(value.cast(), context) }
+    }
+}
+
+/// The archived form of a map is a map of the archived values, so it can be
+/// accessed by key directly.
+impl<K: Key, V: Archive, const N: usize> Archive for Assoc<K, V>
+where
+    K::Impl: KeyImpl<Storage<V> = [V; N]>,
+    K::Impl: KeyImpl<Storage<V::Archived> = [V::Archived; N]>,
+{
+    type Archived = Assoc<K, V::Archived>;
+    type Resolver = [V::Resolver; N];
+
+    fn resolve(&self, resolver: Self::Resolver, out: Place<Self::Archived>) {
+        // SAFETY: `Assoc<K, V::Archived>` is a `#[repr(transparent)]` wrapper
+        // around `[V::Archived; N]`.
+        let out = unsafe { out.cast_unchecked::<[V::Archived; N]>() };
+        self.storage.resolve(resolver, out);
+    }
+}
+
+impl<S: Fallible + ?Sized, K: Key, V: Serialize<S>, const N: usize> Serialize<S> for Assoc<K, V>
+where
+    K::Impl: KeyImpl<Storage<V> = [V; N]>,
+    K::Impl: KeyImpl<Storage<V::Archived> = [V::Archived; N]>,
+{
+    fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        self.storage.serialize(serializer)
+    }
+}
+
+impl<D: Fallible + ?Sized, K: Key, V: Archive, const N: usize> Deserialize<Assoc<K, V>, D>
+    for Assoc<K, V::Archived>
+where
+    K::Impl: KeyImpl<Storage<V> = [V; N]>,
+    K::Impl: KeyImpl<Storage<V::Archived> = [V::Archived; N]>,
+    V::Archived: Deserialize<V, D>,
+{
+    fn deserialize(&self, deserializer: &mut D) -> Result<Assoc<K, V>, D::Error> {
+        let values = Deserialize::<[V; N], D>::deserialize(&self.storage, deserializer)?;
+        Ok(Assoc::from_values(values))
+    }
+}
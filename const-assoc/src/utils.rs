@@ -16,6 +16,16 @@ pub const fn into_usize<T: ConstIntoUSize>(value: T) -> usize {
     }
 }
 
+#[cfg(target_pointer_width = "16")]
+#[inline(always)]
+pub const fn from_usize<T: ConstIntoUSize>(value: usize) -> T {
+    match T::WITNESS {
+        IntoUSizeWitness::U8(te) => te.to_left(value as u8),
+        IntoUSizeWitness::U16(te) => te.to_left(value as u16),
+        IntoUSizeWitness::USize(te) => te.to_left(value),
+    }
+}
+
 #[cfg(target_pointer_width = "16")]
 typewit::simple_type_witness! {
     enum IntoUSizeWitness {
@@ -36,6 +46,17 @@ pub const fn into_usize<T: ConstIntoUSize>(value: T) -> usize {
     }
 }
 
+#[cfg(target_pointer_width = "32")]
+#[inline(always)]
+pub const fn from_usize<T: ConstIntoUSize>(value: usize) -> T {
+    match T::WITNESS {
+        IntoUSizeWitness::U8(te) => te.to_left(value as u8),
+        IntoUSizeWitness::U16(te) => te.to_left(value as u16),
+        IntoUSizeWitness::U32(te) => te.to_left(value as u32),
+        IntoUSizeWitness::USize(te) => te.to_left(value),
+    }
+}
+
 #[cfg(target_pointer_width = "32")]
 typewit::simple_type_witness! {
     enum IntoUSizeWitness {
@@ -58,6 +79,18 @@ pub const fn into_usize<T: ConstIntoUSize>(value: T) -> usize {
     }
 }
 
+#[cfg(target_pointer_width = "64")]
+#[inline(always)]
+pub const fn from_usize<T: ConstIntoUSize>(value: usize) -> T {
+    match T::WITNESS {
+        IntoUSizeWitness::U8(te) => te.to_left(value as u8),
+        IntoUSizeWitness::U16(te) => te.to_left(value as u16),
+        IntoUSizeWitness::U32(te) => te.to_left(value as u32),
+        IntoUSizeWitness::U64(te) => te.to_left(value as u64),
+        IntoUSizeWitness::USize(te) => te.to_left(value),
+    }
+}
+
 #[cfg(target_pointer_width = "64")]
 typewit::simple_type_witness! {
     enum IntoUSizeWitness {
@@ -86,12 +119,26 @@ where
     transmute_copy_safe(&src)
 }
 
+/// Transmutes `src` to `Dst` without any compile-time guarantees.
+///
+/// # Safety
+/// The caller must ensure that `src` is a valid bit pattern for `Dst` and
+/// that `Src` and `Dst` have the same size.
+#[inline(always)]
+pub const unsafe fn transmute_unchecked<Src: Copy, Dst: Copy>(src: Src) -> Dst {
+    const { assert!(mem::size_of::<Src>() == mem::size_of::<Dst>()) };
+    unsafe { mem::transmute_copy(&src) }
+}
+
 #[inline(always)]
 pub const fn transmute_copy_safe<Src, Dst>(src: &Src) -> Dst
 where
     Src: TransmuteSafe<Dst>,
     Dst: Copy,
 {
+    // `mem::transmute_copy` doesn't check that the sizes match, so turn any
+    // mismatched `TransmuteSafe` impl into a compile-time error.
+    const { assert!(mem::size_of::<Src>() == mem::size_of::<Dst>()) };
     unsafe { mem::transmute_copy(src) }
 }
 
@@ -122,3 +169,46 @@ pub const unsafe fn assume_init_array<T, const N: usize>(array: [MaybeUninit<T>;
     let ptr: *const [T; N] = array.as_ptr() as *const [T; N];
     unsafe { ptr.cast::<[T; N]>().read() }
 }
+
+/// Collects exactly `N` results from `iter` into an array, returning the first
+/// error instead if there is one.
+///
+/// Values collected before an error (or a panic in `iter`) are dropped.
+///
+/// # Safety
+/// The caller must ensure that `iter` yields at least `N` items.
+pub unsafe fn try_collect_array<T, E, const N: usize>(
+    mut iter: impl Iterator<Item = Result<T, E>>,
+) -> Result<[T; N], E> {
+    struct Guard<T, const N: usize> {
+        array: [MaybeUninit<T>; N],
+        initialized: usize,
+    }
+
+    impl<T, const N: usize> Drop for Guard<T, N> {
+        fn drop(&mut self) {
+            for value in &mut self.array[..self.initialized] {
+                // SAFETY: The first `initialized` elements are initialized.
+                unsafe { value.assume_init_drop() };
+            }
+        }
+    }
+
+    let mut guard = Guard {
+        array: [const { MaybeUninit::uninit() }; N],
+        initialized: 0,
+    };
+
+    while guard.initialized < N {
+        // SAFETY: The caller guarantees that `iter` yields at least `N` items.
+        let value = unsafe { iter.next().unwrap_unchecked() }?;
+        guard.array[guard.initialized].write(value);
+        guard.initialized += 1;
+    }
+
+    let array = mem::replace(&mut guard.array, [const { MaybeUninit::uninit() }; N]);
+    mem::forget(guard);
+
+    // SAFETY: The loop above initialized all `N` elements.
+    Ok(unsafe { assume_init_array(array) })
+}
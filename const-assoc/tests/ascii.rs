@@ -0,0 +1,31 @@
+use const_assoc::{AsciiRange, Assoc};
+
+type Digit = AsciiRange<b'0', 10>;
+
+#[test]
+fn new_checks_range() {
+    assert_eq!(Digit::new(b'0').map(Digit::byte), Some(b'0'));
+    assert_eq!(Digit::new(b'9').map(Digit::to_char), Some('9'));
+    assert_eq!(Digit::new(b'/'), None);
+    assert_eq!(Digit::new(b':'), None);
+    assert_eq!(Digit::from_char('é'), None);
+}
+
+#[test]
+fn map_keyed_by_ascii_range() {
+    const VALUES: Assoc<Digit, u32> = Assoc::from_values([0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+
+    assert_eq!(VALUES.len(), 10);
+    assert_eq!(VALUES[Digit::from_char('7').unwrap()], 7);
+
+    let keys: String = VALUES.keys().map(Digit::to_char).collect();
+    assert_eq!(keys, "0123456789");
+}
+
+#[test]
+fn debug_shows_char() {
+    assert_eq!(
+        format!("{:?}", Digit::new(b'4').unwrap()),
+        "AsciiRange('4')"
+    );
+}
@@ -0,0 +1,821 @@
+use const_assoc::{assoc, assoc_from_fn, Assoc, PrimitiveEnum};
+
+#[repr(u8)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PrimitiveEnum)]
+enum Letter {
+    A,
+    B,
+    C,
+}
+
+const LETTERS: Assoc<Letter, char> = assoc! {
+    Letter::A => 'a',
+    Letter::B => 'b',
+    Letter::C => 'c',
+};
+
+#[test]
+fn values_array_in_const() {
+    const VALUES: [char; 3] = LETTERS.values_array();
+    assert_eq!(VALUES, ['a', 'b', 'c']);
+}
+
+#[test]
+fn debug_formats_as_map() {
+    assert_eq!(format!("{:?}", LETTERS), "{A: 'a', B: 'b', C: 'c'}");
+}
+
+#[test]
+fn swap_with_swaps_single_key() {
+    let mut current = LETTERS;
+    let mut previous = assoc! {
+        Letter::A => 'x',
+        Letter::B => 'y',
+        Letter::C => 'z',
+    };
+
+    current.swap_with(&mut previous, Letter::B);
+
+    assert_eq!(current.values_array(), ['a', 'y', 'c']);
+    assert_eq!(previous.values_array(), ['x', 'b', 'z']);
+}
+
+#[test]
+fn clone_from_reuses_value_allocations() {
+    let source = assoc! {
+        Letter::A => vec![1],
+        Letter::B => vec![2],
+        Letter::C => vec![3],
+    };
+
+    let mut target = assoc! {
+        Letter::A => Vec::<i32>::with_capacity(64),
+        Letter::B => Vec::<i32>::with_capacity(64),
+        Letter::C => Vec::<i32>::with_capacity(64),
+    };
+
+    target.clone_from(&source);
+
+    assert_eq!(target[Letter::B], [2]);
+    assert!(target.values().all(|values| values.capacity() >= 64));
+}
+
+#[test]
+fn get_many_unchecked_mut() {
+    let mut letters = LETTERS;
+
+    // SAFETY: `Letter::A` and `Letter::C` are distinct keys.
+    let [a, c] = unsafe { letters.get_many_unchecked_mut([Letter::A, Letter::C]) };
+    core::mem::swap(a, c);
+
+    assert_eq!(letters.values_array(), ['c', 'b', 'a']);
+}
+
+#[test]
+fn indexed_yields_index_key_and_value() {
+    let mut entries = LETTERS.indexed();
+
+    assert_eq!(entries.next(), Some((0, Letter::A, &'a')));
+    assert_eq!(entries.next(), Some((1, Letter::B, &'b')));
+    assert_eq!(entries.next(), Some((2, Letter::C, &'c')));
+    assert_eq!(entries.next(), None);
+}
+
+#[test]
+fn map_ref_keeps_source() {
+    let names = assoc! {
+        Letter::A => String::from("alpha"),
+        Letter::B => String::from("beta"),
+        Letter::C => String::from("gamma"),
+    };
+
+    let lengths = names.map_ref(String::len);
+
+    assert_eq!(lengths.values_array(), [5, 4, 5]);
+    assert_eq!(names[Letter::B], "beta");
+}
+
+#[test]
+fn keys_are_in_discriminant_order() {
+    assert!(LETTERS.keys().eq([Letter::A, Letter::B, Letter::C]));
+    assert!(LETTERS.into_keys().eq([Letter::A, Letter::B, Letter::C]));
+}
+
+#[test]
+fn reduce_combines_all_values() {
+    let counters = assoc! {
+        Letter::A => 3,
+        Letter::B => 4,
+        Letter::C => 5,
+    };
+
+    assert_eq!(counters.reduce(|a, b| a + b), Some(12));
+}
+
+#[test]
+fn compares_against_arrays() {
+    assert_eq!(LETTERS, ['a', 'b', 'c']);
+    assert_eq!(['a', 'b', 'c'], LETTERS);
+    assert_ne!(LETTERS, ['a', 'b', 'x']);
+}
+
+#[test]
+fn zip_and_unzip_round_trip() {
+    let numbers = assoc! {
+        Letter::A => 1,
+        Letter::B => 2,
+        Letter::C => 3,
+    };
+
+    let zipped = LETTERS.zip(numbers);
+    assert_eq!(zipped, [('a', 1), ('b', 2), ('c', 3)]);
+
+    let (letters, numbers) = zipped.unzip();
+    assert_eq!(letters, LETTERS);
+    assert_eq!(numbers, [1, 2, 3]);
+}
+
+#[test]
+fn entry_and_update() {
+    let mut counters = assoc! {
+        Letter::A => 0,
+        Letter::B => 0,
+        Letter::C => 0,
+    };
+
+    *counters.entry(Letter::A) += 2;
+    counters.update(Letter::C, |count| *count += 5);
+
+    assert_eq!(counters, [2, 0, 5]);
+}
+
+#[test]
+fn from_fn_visits_keys_in_order() {
+    let mut visited = Vec::new();
+    let map = Assoc::from_fn(|key: Letter| {
+        visited.push(key);
+        visited.len()
+    });
+
+    assert_eq!(visited, [Letter::A, Letter::B, Letter::C]);
+    assert_eq!(map, [1, 2, 3]);
+
+    let mut visited = Vec::new();
+    let map = Assoc::from_fn_rev(|key: Letter| {
+        visited.push(key);
+        visited.len()
+    });
+
+    assert_eq!(visited, [Letter::C, Letter::B, Letter::A]);
+    assert_eq!(map, [3, 2, 1]);
+}
+
+#[test]
+fn get_pair_mut_returns_requested_order() {
+    let mut letters = LETTERS;
+
+    let (c, a) = letters.get_pair_mut(Letter::C, Letter::A);
+    assert_eq!((*c, *a), ('c', 'a'));
+    *c = 'x';
+
+    assert_eq!(letters, ['a', 'b', 'x']);
+}
+
+#[test]
+#[should_panic]
+fn get_pair_mut_panics_on_same_key() {
+    let mut letters = LETTERS;
+    letters.get_pair_mut(Letter::B, Letter::B);
+}
+
+#[test]
+fn const_values_in_const() {
+    const SECOND: char = LETTERS.const_values()[1];
+    const LEN: usize = LETTERS.const_values().len();
+
+    assert_eq!(SECOND, 'b');
+    assert_eq!(LEN, 3);
+}
+
+#[test]
+fn fill_from_slice_checks_length() {
+    let mut letters = LETTERS;
+
+    letters.fill_from_slice(&['x', 'y', 'z']);
+    assert_eq!(letters, ['x', 'y', 'z']);
+
+    let err = letters.try_fill_from_slice(&['a']).unwrap_err();
+    assert_eq!((err.expected, err.actual), (3, 1));
+    assert_eq!(letters, ['x', 'y', 'z']);
+}
+
+#[repr(u8)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PrimitiveEnum)]
+enum Digit {
+    Zero,
+    One,
+    Two,
+    Three,
+}
+
+#[test]
+fn map_keys_relocates_values() {
+    let digits = LETTERS.map_keys(|letter| match letter {
+        Letter::A => Digit::Three,
+        Letter::B => Digit::Zero,
+        Letter::C => Digit::One,
+    });
+
+    assert_eq!(digits, ['b', 'c', '\0', 'a']);
+    assert_eq!(digits[Digit::Two], char::default());
+}
+
+#[test]
+#[should_panic]
+fn map_keys_panics_on_collision() {
+    let _ = LETTERS.map_keys(|letter| match letter {
+        Letter::A | Letter::B => Digit::One,
+        Letter::C => Digit::Two,
+    });
+}
+
+#[test]
+fn filter_map_values_keeps_shape() {
+    let vowels = LETTERS.filter_map_values(|c| (c == 'a').then(|| c.to_ascii_uppercase()));
+    assert_eq!(vowels, [Some('A'), None, None]);
+}
+
+const fn letter_to_upper(letter: Letter) -> char {
+    match letter {
+        Letter::A => 'A',
+        Letter::B => 'B',
+        Letter::C => 'C',
+    }
+}
+
+#[test]
+fn assoc_from_fn_in_const() {
+    const UPPER: Assoc<Letter, char> = assoc_from_fn!(letter_to_upper);
+    assert_eq!(UPPER, ['A', 'B', 'C']);
+}
+
+#[test]
+fn ordering_is_lexicographic_by_discriminant() {
+    let a: Assoc<Letter, u8> = Assoc::from_values([1, 5, 0]);
+    let b: Assoc<Letter, u8> = Assoc::from_values([1, 2, 9]);
+    assert!(b < a);
+    assert_eq!(a.cmp(&a.clone()), std::cmp::Ordering::Equal);
+
+    let mut sorted = [a, b];
+    sorted.sort();
+    assert_eq!(sorted, [b, a]);
+}
+
+#[test]
+fn zip_with_combines_values() {
+    let a: Assoc<Letter, u32> = Assoc::from_values([1, 2, 3]);
+    let b: Assoc<Letter, u32> = Assoc::from_values([10, 20, 30]);
+    assert_eq!(a.zip_with(b, |x, y| x * y), [10, 40, 90]);
+}
+
+#[test]
+fn splat_in_const() {
+    const MAXES: Assoc<Letter, u32> = Assoc::splat(u32::MAX);
+    assert_eq!(MAXES, [u32::MAX; 3]);
+}
+
+#[test]
+fn try_get_returns_some_for_every_key() {
+    let mut map: Assoc<Letter, u8> = Assoc::from_values([1, 2, 3]);
+    assert_eq!(map.try_get(Letter::B), Some(&2));
+    assert_eq!(map.get_or(Letter::A, &0), &1);
+
+    *map.try_get_mut(Letter::C).unwrap() = 7;
+    assert_eq!(map, [1, 2, 7]);
+}
+
+#[test]
+fn split_at_partitions_by_discriminant() {
+    let map: Assoc<Letter, u8> = Assoc::from_values([1, 2, 3]);
+    let (left, right) = map.split_at(Letter::B);
+    assert_eq!(left, [Some(1), None, None]);
+    assert_eq!(right, [None, Some(2), Some(3)]);
+}
+
+#[test]
+fn iter_values_with_index_pairs_indices() {
+    let mut map: Assoc<Letter, u8> = Assoc::from_values([4, 5, 6]);
+    for (idx, value) in map.iter_values_with_index_mut() {
+        *value += idx as u8;
+    }
+
+    let pairs: Vec<_> = map.iter_values_with_index().collect();
+    assert_eq!(pairs, [(0, &4), (1, &6), (2, &8)]);
+}
+
+#[test]
+fn apply_overwrites_only_some_entries() {
+    let mut map: Assoc<Letter, u8> = Assoc::from_values([1, 2, 3]);
+    let overrides = Assoc::from_values([None, Some(20), None]);
+    map.apply(&overrides);
+    assert_eq!(map, [1, 20, 3]);
+}
+
+#[test]
+fn clear_resets_to_default() {
+    let mut counts: Assoc<Letter, Option<usize>> = Assoc::from_values([Some(1), None, Some(3)]);
+    counts.clear();
+    assert_eq!(counts, [None; 3]);
+
+    const CLEARED: Assoc<Letter, u8> = {
+        let mut map = Assoc::from_values([1, 2, 3]);
+        map.const_clear();
+        map
+    };
+    assert_eq!(CLEARED, [0; 3]);
+}
+
+#[test]
+fn into_values_is_double_ended_and_exact_size() {
+    struct Holder {
+        values: const_assoc::IntoValues<u8, 3>,
+    }
+
+    let map: Assoc<Letter, u8> = Assoc::from_values([1, 2, 3]);
+    let mut holder = Holder {
+        values: map.into_values(),
+    };
+    assert_eq!(holder.values.len(), 3);
+    assert_eq!(holder.values.next_back(), Some(3));
+    assert_eq!(holder.values.next(), Some(1));
+    assert_eq!(holder.values.len(), 1);
+}
+
+#[test]
+fn position_of_finds_lowest_key() {
+    let map: Assoc<Letter, u8> = Assoc::from_values([1, 2, 2]);
+    assert_eq!(map.position_of(&2), Some(Letter::B));
+    assert_eq!(map.position_of(&9), None);
+}
+
+#[test]
+fn try_from_pairs() {
+    use const_assoc::FromPairsError;
+
+    let map = Assoc::try_from(&[(Letter::C, 3), (Letter::A, 1), (Letter::B, 2)][..]).unwrap();
+    assert_eq!(map, [1, 2, 3]);
+
+    let duplicate = Assoc::<Letter, u8>::try_from(&[(Letter::A, 1), (Letter::A, 2)][..]);
+    assert!(matches!(
+        duplicate,
+        Err(FromPairsError::DuplicateKey(Letter::A))
+    ));
+
+    let missing = Assoc::<Letter, u8>::try_from(&[(Letter::B, 2)][..]).unwrap_err();
+    assert_eq!(missing.to_string(), "missing keys [A, C]");
+    match missing {
+        FromPairsError::MissingKeys(missing) => assert_eq!(missing, [true, false, true]),
+        FromPairsError::DuplicateKey(_) => panic!("expected missing keys"),
+    }
+}
+
+#[test]
+fn value_windows_yields_adjacent_pairs() {
+    let map: Assoc<Letter, i32> = Assoc::from_values([1, 4, 9]);
+    let deltas: Vec<_> = map.value_windows().map(|(a, b)| b - a).collect();
+    assert_eq!(deltas, [3, 5]);
+}
+
+#[test]
+fn sum_and_product() {
+    const COUNTS: Assoc<Letter, u32> = Assoc::from_values([2, 3, 4]);
+    const TOTAL: u32 = COUNTS.const_sum();
+    const PRODUCT: u32 = COUNTS.const_product();
+
+    assert_eq!(TOTAL, 9);
+    assert_eq!(PRODUCT, 24);
+    assert_eq!(COUNTS.sum(), TOTAL);
+    assert_eq!(COUNTS.product(), PRODUCT);
+
+    let signed: Assoc<Letter, i64> = Assoc::from_values([-1, 5, -2]);
+    assert_eq!(signed.const_sum(), 2);
+    assert_eq!(signed.const_product(), 10);
+}
+
+#[test]
+fn replace_all_with_transforms_in_place() {
+    let mut map: Assoc<Letter, Option<u8>> = Assoc::from_values([Some(1), None, Some(3)]);
+    map.replace_all_with(|value| value.map(|x| x * 2).or(Some(0)));
+    assert_eq!(map, [Some(2), Some(0), Some(6)]);
+}
+
+#[test]
+fn set_and_with_chain() {
+    let mut map: Assoc<Letter, u8> = Assoc::splat(0);
+    map.set(Letter::A, 1).set(Letter::C, 3);
+    assert_eq!(map, [1, 0, 3]);
+
+    let map = map.with(Letter::B, 2).with(Letter::A, 4);
+    assert_eq!(map, [4, 2, 3]);
+}
+
+#[test]
+fn rotate_shifts_values_between_keys() {
+    let mut map: Assoc<Letter, u8> = Assoc::from_values([1, 2, 3]);
+    map.rotate_left(1);
+    assert_eq!(map, [2, 3, 1]);
+    map.rotate_right(4);
+    assert_eq!(map, [1, 2, 3]);
+}
+
+#[test]
+fn fmt_into_uses_custom_key_names() {
+    struct Named(Assoc<Letter, u8>);
+
+    impl std::fmt::Debug for Named {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            self.0.fmt_into(f, |letter| match letter {
+                Letter::A => "alpha",
+                Letter::B => "bravo",
+                Letter::C => "charlie",
+            })
+        }
+    }
+
+    let named = Named(Assoc::from_values([1, 2, 3]));
+    assert_eq!(format!("{named:?}"), "{alpha: 1, bravo: 2, charlie: 3}");
+}
+
+#[test]
+fn select_projects_listed_keys() {
+    let map: Assoc<Letter, u8> = Assoc::from_values([1, 2, 3]);
+    let selected = map.select(&[Letter::C, Letter::A]);
+    assert_eq!(selected, [Some(&1), None, Some(&3)]);
+}
+
+#[test]
+fn from_default_then_applies_overrides() {
+    let map = Assoc::<Letter, u8>::from_default_then(|map| {
+        map.set(Letter::B, 5);
+    });
+    assert_eq!(map, [0, 5, 0]);
+}
+
+#[test]
+fn truncate_to_drops_trailing_values() {
+    use std::rc::Rc;
+
+    #[repr(u8)]
+    #[derive(Copy, Clone, Debug, PartialEq, PrimitiveEnum)]
+    enum Pair {
+        First,
+        Second,
+    }
+
+    let tracker = Rc::new(());
+    let map: Assoc<Letter, Rc<()>> = Assoc::from_fn(|_| tracker.clone());
+    assert_eq!(Rc::strong_count(&tracker), 4);
+
+    let truncated: Assoc<Pair, Rc<()>> = map.truncate_to();
+    assert_eq!(Rc::strong_count(&tracker), 3);
+    assert_eq!(
+        truncated.keys().collect::<Vec<_>>(),
+        [Pair::First, Pair::Second]
+    );
+}
+
+#[test]
+fn get_by_repr_checks_bounds() {
+    let map: Assoc<Letter, char> = Assoc::from_values(['a', 'b', 'c']);
+    assert_eq!(map.get_by_repr(Letter::C as u8), Some(&'c'));
+    assert_eq!(map.get_by_repr(3), None);
+    // SAFETY: `1` is the discriminant of `Letter::B`.
+    assert_eq!(unsafe { map.get_by_repr_unchecked(1) }, &'b');
+}
+
+#[test]
+fn values_chunks_split_storage() {
+    let mut map: Assoc<Letter, u8> = Assoc::from_values([1, 2, 3]);
+    for chunk in map.values_chunks_mut(2) {
+        chunk.reverse();
+    }
+
+    let chunks: Vec<_> = map.values_chunks(2).collect();
+    assert_eq!(chunks, [&[2, 1][..], &[3][..]]);
+}
+
+#[test]
+fn from_partial_iter_marks_missing_keys() {
+    let map = Assoc::from_partial_iter([(Letter::C, 3), (Letter::A, 1), (Letter::C, 4)]);
+    assert_eq!(map, [Some(1), None, Some(4)]);
+}
+
+#[test]
+fn min_and_max_value_break_ties_by_discriminant() {
+    let map: Assoc<Letter, u8> = Assoc::from_values([1, 1, 1]);
+    assert!(std::ptr::eq(map.min_value(), &map[Letter::A]));
+    assert!(std::ptr::eq(map.max_value(), &map[Letter::C]));
+
+    let map: Assoc<Letter, u8> = Assoc::from_values([5, 2, 9]);
+    assert_eq!((map.min_value(), map.max_value()), (&2, &9));
+}
+
+#[test]
+fn take_resets_to_default() {
+    let mut map: Assoc<Letter, u8> = Assoc::from_values([1, 2, 3]);
+    assert_eq!(map.take(Letter::B), 2);
+    assert_eq!(map, [1, 0, 3]);
+}
+
+#[test]
+fn borrows_arrays_as_maps() {
+    const VALUES: [u8; 3] = [1, 2, 3];
+    const VIEW: &Assoc<Letter, u8> = Assoc::from_array_ref(&VALUES);
+    assert_eq!(VIEW[Letter::B], 2);
+
+    let mut values = [1, 2, 3];
+    Assoc::<Letter, u8>::from_array_mut(&mut values)[Letter::C] = 7;
+    assert_eq!(values, [1, 2, 7]);
+}
+
+#[test]
+fn counts_by_value_tallies_keys() {
+    let map: Assoc<Digit, Letter> =
+        Assoc::from_values([Letter::B, Letter::A, Letter::B, Letter::B]);
+    assert_eq!(map.counts_by_value(), [1, 3, 0]);
+}
+
+#[test]
+fn shift_keys_moves_values_to_later_keys() {
+    let map: Assoc<Letter, char> = Assoc::from_values(['a', 'b', 'c']);
+    let shifted = map.shift_keys(1);
+    assert_eq!(shifted[Letter::B], 'a');
+    assert_eq!(shifted, ['c', 'a', 'b']);
+    assert_eq!(shifted.shift_keys(5), ['a', 'b', 'c']);
+}
+
+#[test]
+fn map_values_indexed_passes_indices() {
+    let map: Assoc<Letter, u32> = Assoc::from_values([10, 20, 30]);
+    assert_eq!(
+        map.map_values_indexed(|idx, value| value * idx as u32),
+        [0, 20, 60]
+    );
+}
+
+#[test]
+fn into_values_rev_starts_at_highest_key() {
+    let map: Assoc<Letter, u8> = Assoc::from_values([1, 2, 3]);
+    assert_eq!(map.into_values_rev().collect::<Vec<_>>(), [3, 2, 1]);
+}
+
+#[test]
+fn eq_ignoring_skips_listed_keys() {
+    let a: Assoc<Letter, u8> = Assoc::from_values([1, 2, 3]);
+    let b: Assoc<Letter, u8> = Assoc::from_values([1, 9, 3]);
+    assert!(a.eq_ignoring(&b, &[Letter::B]));
+    assert!(!a.eq_ignoring(&b, &[Letter::A, Letter::C]));
+}
+
+#[test]
+fn prefix_scan_accumulates_in_order() {
+    let map: Assoc<Letter, u32> = Assoc::from_values([1, 2, 3]);
+    assert_eq!(map.prefix_scan(0, |acc, value| acc + value), [1, 3, 6]);
+
+    let names = map.prefix_scan(String::new(), |acc, value| format!("{acc}{value}"));
+    assert_eq!(
+        names,
+        ["1".to_string(), "12".to_string(), "123".to_string()]
+    );
+}
+
+#[test]
+fn from_entries_checked_reports_every_problem() {
+    let map = Assoc::from_entries_checked([(Letter::B, 2), (Letter::A, 1), (Letter::C, 3)]);
+    assert_eq!(map.unwrap(), [1, 2, 3]);
+
+    let error =
+        Assoc::<Letter, u8>::from_entries_checked([(Letter::B, 2), (Letter::B, 3)]).unwrap_err();
+    assert_eq!(error.missing().collect::<Vec<_>>(), [Letter::A, Letter::C]);
+    assert_eq!(error.duplicated().collect::<Vec<_>>(), [Letter::B]);
+    assert_eq!(
+        error.to_string(),
+        "missing keys [A, C], duplicated keys [B]"
+    );
+}
+
+#[test]
+fn fold_keys_builds_mask_in_order() {
+    let map: Assoc<Letter, bool> = Assoc::from_values([true, false, true]);
+    let mask = map.fold_keys(0u8, |mask, key| {
+        if map[key] {
+            mask | 1 << key as u8
+        } else {
+            mask
+        }
+    });
+    assert_eq!(mask, 0b101);
+}
+
+#[test]
+fn with_values_replaces_all_values() {
+    let map: Assoc<Letter, u8> = Assoc::from_values([1, 2, 3]);
+    let map = map.with_values(["x", "y", "z"]);
+    assert_eq!(map[Letter::B], "y");
+}
+
+#[test]
+fn saturating_add_and_sub_clamp_at_bounds() {
+    let mut map: Assoc<Letter, u8> = Assoc::from_values([250, 5, 0]);
+    map.saturating_add(Letter::A, 10);
+    map.saturating_sub(Letter::C, 1);
+    map.saturating_add(Letter::B, 1);
+    assert_eq!(map.values_array(), [u8::MAX, 6, 0]);
+
+    let mut map: Assoc<Letter, i8> = Assoc::from_values([i8::MIN + 1, i8::MAX, 0]);
+    map.saturating_sub(Letter::A, 2);
+    map.saturating_add(Letter::B, 1);
+    map.saturating_sub(Letter::C, 3);
+    assert_eq!(map.values_array(), [i8::MIN, i8::MAX, -3]);
+}
+
+#[test]
+fn try_fold_stops_at_first_error() {
+    let map: Assoc<Letter, i32> = Assoc::from_values([1, -2, 3]);
+
+    let mut visited = Vec::new();
+    let result = map.try_fold(0, |acc, key, &value| {
+        visited.push(key);
+        if value < 0 {
+            Err(key)
+        } else {
+            Ok(acc + value)
+        }
+    });
+
+    assert_eq!(result, Err(Letter::B));
+    assert_eq!(visited, [Letter::A, Letter::B]);
+
+    let map: Assoc<Letter, i32> = Assoc::from_values([1, 2, 3]);
+    assert_eq!(
+        map.try_fold(0, |acc, _, &value| Ok::<_, ()>(acc + value)),
+        Ok(6)
+    );
+}
+
+#[test]
+fn nth_key_and_value() {
+    let mut map: Assoc<Letter, u8> = Assoc::from_values([1, 2, 3]);
+
+    assert_eq!(map.nth_key(0), Some(Letter::A));
+    assert_eq!(map.nth_key(2), Some(Letter::C));
+    assert_eq!(map.nth_key(3), None);
+
+    assert_eq!(map.nth_value(1), Some(&2));
+    assert_eq!(map.nth_value(3), None);
+
+    *map.nth_value_mut(2).unwrap() = 30;
+    assert_eq!(map[Letter::C], 30);
+    assert_eq!(map.nth_value_mut(3), None);
+}
+
+#[test]
+fn keys_matching_predicate() {
+    let map: Assoc<Letter, u8> = Assoc::from_values([1, 4, 6]);
+
+    assert_eq!(map.key_of_first(|&value| value % 2 == 0), Some(Letter::B));
+    assert_eq!(map.key_of_first(|&value| value > 10), None);
+
+    let even: Vec<_> = map.all_keys_matching(|&value| value % 2 == 0).collect();
+    assert_eq!(even, [Letter::B, Letter::C]);
+}
+
+#[test]
+fn map_err_values_only_touches_errors() {
+    let map: Assoc<Letter, Result<u8, &str>> = Assoc::from_values([Ok(1), Err("bad"), Ok(3)]);
+    let map = map.map_err_values(str::len);
+    assert_eq!(map.values_array(), [Ok(1), Err(3), Ok(3)]);
+}
+
+#[test]
+fn collect_results_returns_first_error() {
+    use std::rc::Rc;
+
+    let ok: Assoc<Letter, Result<u8, &str>> = Assoc::from_values([Ok(1), Ok(2), Ok(3)]);
+    assert_eq!(ok.collect_results().unwrap().values_array(), [1, 2, 3]);
+
+    let value = Rc::new(());
+    let failed: Assoc<Letter, Result<Rc<()>, &str>> =
+        Assoc::from_values([Ok(value.clone()), Err("b"), Err("c")]);
+    assert_eq!(failed.collect_results().err(), Some("b"));
+    assert_eq!(Rc::strong_count(&value), 1);
+}
+
+#[test]
+fn iter_selected_follows_given_order() {
+    let map: Assoc<Letter, u8> = Assoc::from_values([1, 2, 3]);
+    let selected: Vec<_> = map.iter_selected(&[Letter::C, Letter::A]).collect();
+    assert_eq!(selected, [(Letter::C, &3), (Letter::A, &1)]);
+}
+
+#[test]
+fn default_supports_many_keys() {
+    #[rustfmt::skip]
+    #[allow(dead_code)]
+    #[repr(u8)]
+    #[derive(Copy, Clone, PrimitiveEnum)]
+    enum Wide {
+        K0, K1, K2, K3, K4, K5, K6, K7, K8, K9, K10, K11, K12, K13, K14, K15, K16,
+        K17, K18, K19, K20, K21, K22, K23, K24, K25, K26, K27, K28, K29, K30, K31, K32,
+    }
+
+    let map: Assoc<Wide, String> = Assoc::default();
+    assert_eq!(map.len(), 33);
+    assert!(map[Wide::K32].is_empty());
+}
+
+#[test]
+fn permute_moves_values_to_target_keys() {
+    let mut map: Assoc<Letter, u8> = Assoc::from_values([1, 2, 3]);
+    let perm = assoc! {
+        Letter::A => Letter::C,
+        Letter::B => Letter::A,
+        Letter::C => Letter::B,
+    };
+    map.permute(&perm);
+    assert_eq!(map.values_array(), [2, 3, 1]);
+
+    let swap = assoc! {
+        Letter::A => Letter::B,
+        Letter::B => Letter::A,
+        Letter::C => Letter::C,
+    };
+    map.permute(&swap);
+    assert_eq!(map.values_array(), [3, 2, 1]);
+}
+
+#[test]
+#[should_panic(expected = "`perm` is not a permutation")]
+fn permute_rejects_non_bijective_maps() {
+    let mut map: Assoc<Letter, u8> = Assoc::from_values([1, 2, 3]);
+    let perm = assoc! {
+        Letter::A => Letter::B,
+        Letter::B => Letter::B,
+        Letter::C => Letter::C,
+    };
+    map.permute(&perm);
+}
+
+#[test]
+fn debug_non_default_elides_default_values() {
+    let map: Assoc<Letter, u8> = Assoc::from_values([0, 5, 0]);
+    assert_eq!(format!("{:?}", map.debug_non_default()), "{B: 5, ..}");
+
+    let map: Assoc<Letter, u8> = Assoc::from_values([1, 2, 3]);
+    assert_eq!(
+        format!("{:?}", map.debug_non_default()),
+        "{A: 1, B: 2, C: 3}"
+    );
+}
+
+#[test]
+fn into_entries_pairs_keys_with_values() {
+    let map: Assoc<Letter, String> = Assoc::from_fn(|letter| format!("{letter:?}"));
+    assert_eq!(
+        map.into_entries(),
+        [
+            (Letter::A, "A".to_string()),
+            (Letter::B, "B".to_string()),
+            (Letter::C, "C".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn mask_keeps_selected_keys() {
+    let map: Assoc<Letter, u8> = Assoc::from_values([1, 2, 3]);
+    let masked = map.mask(|letter| letter != Letter::B);
+    assert_eq!(masked.values_array(), [Some(1), None, Some(3)]);
+}
+
+#[test]
+fn get2_and_get_many() {
+    let map: Assoc<Letter, u8> = Assoc::from_values([1, 2, 3]);
+    assert_eq!(map.get2(Letter::C, Letter::A), (&3, &1));
+    assert_eq!(map.get2(Letter::B, Letter::B), (&2, &2));
+    assert_eq!(
+        map.get_many([Letter::B, Letter::C, Letter::B]),
+        [&2, &3, &2]
+    );
+}
+
+#[test]
+fn try_into_array_checks_length() {
+    let map: Assoc<Letter, String> = Assoc::from_fn(|letter| format!("{letter:?}"));
+
+    let map = map.try_into_array::<2>().unwrap_err();
+    assert_eq!(map[Letter::C], "C");
+
+    let array: [String; 3] = map.try_into_array().unwrap();
+    assert_eq!(array, ["A", "B", "C"]);
+}
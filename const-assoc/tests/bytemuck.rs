@@ -0,0 +1,45 @@
+#![cfg(feature = "bytemuck")]
+
+use const_assoc::{assoc, Assoc, PrimitiveEnum};
+
+#[repr(u8)]
+#[derive(Copy, Clone, Debug, PrimitiveEnum)]
+enum Channel {
+    Red,
+    Green,
+    Blue,
+}
+
+#[test]
+fn cast_values() {
+    let floats = assoc! {
+        Channel::Red => 1.0f32,
+        Channel::Green => 0.5f32,
+        Channel::Blue => -2.0f32,
+    };
+
+    let bits = floats.cast_values::<u32>();
+
+    assert_eq!(bits[Channel::Red], 1.0f32.to_bits());
+    assert_eq!(bits[Channel::Green], 0.5f32.to_bits());
+    assert_eq!(bits[Channel::Blue], (-2.0f32).to_bits());
+}
+
+#[test]
+fn zeroed_in_const() {
+    const ZEROS: Assoc<Channel, f32> = Assoc::zeroed();
+
+    assert_eq!(ZEROS[Channel::Red], 0.0);
+    assert_eq!(ZEROS[Channel::Green], 0.0);
+    assert_eq!(ZEROS[Channel::Blue], 0.0);
+}
+
+#[test]
+fn bytes_round_trip() {
+    let values: Assoc<Channel, u16> = Assoc::from_values([1, 0x0203, 0xFFFF]);
+    let bytes = values.as_bytes();
+
+    assert_eq!(bytes.len(), 6);
+    assert_eq!(Assoc::<Channel, u16>::try_from_bytes(bytes), Some(&values));
+    assert_eq!(Assoc::<Channel, u16>::try_from_bytes(&bytes[..4]), None);
+}
@@ -0,0 +1,25 @@
+#![cfg(feature = "memchr")]
+
+use const_assoc::{Assoc, PrimitiveEnum};
+
+#[allow(dead_code)]
+#[repr(u8)]
+#[derive(Copy, Clone, Debug, PartialEq, PrimitiveEnum)]
+enum Symbol {
+    Zero,
+    One,
+    Two,
+    Three,
+}
+
+#[test]
+fn position_of_byte_matches_position_of() {
+    let table: Assoc<Symbol, u8> = Assoc::from_values([b'0', b'1', b'1', b'3']);
+
+    for byte in [b'0', b'1', b'3', b'x'] {
+        assert_eq!(table.position_of_byte(byte), table.position_of(&byte));
+    }
+
+    assert_eq!(table.position_of_byte(b'1'), Some(Symbol::One));
+    assert_eq!(table.position_of_byte(b'x'), None);
+}
@@ -1,4 +1,4 @@
-use const_assoc::{assoc, PrimitiveEnum};
+use const_assoc::{assoc, Assoc, PrimitiveEnum, PrimitiveEnumExt};
 
 macro_rules! enum_tests {
     ($($test_name:ident => $repr:ty),* $(,)?) => {
@@ -55,3 +55,306 @@ enum_tests! {
     enum_u64 => u64,
     enum_usize => usize,
 }
+
+mod validate {
+    use const_assoc::{validate, LayoutError, PrimitiveEnum, PrimitiveEnumLayout};
+
+    #[repr(u8)]
+    #[derive(Copy, Clone, PrimitiveEnum)]
+    enum Derived {
+        A,
+        B,
+        C,
+    }
+
+    #[repr(u8)]
+    #[derive(Copy, Clone)]
+    enum Manual {
+        A,
+        B,
+    }
+
+    unsafe impl PrimitiveEnum for Manual {
+        type Layout = PrimitiveEnumLayout<u8, 3>;
+    }
+
+    #[test]
+    fn derived_layout_is_valid() {
+        assert_eq!(validate(&[Derived::A, Derived::B, Derived::C]), Ok(()));
+    }
+
+    #[test]
+    fn detects_wrong_manual_layout() {
+        assert_eq!(
+            validate(&[Manual::A, Manual::B]),
+            Err(LayoutError::MissingDiscriminant { discriminant: 2 })
+        );
+        assert_eq!(
+            validate(&[Manual::A, Manual::B, Manual::A]),
+            Err(LayoutError::DuplicateDiscriminant { discriminant: 0 })
+        );
+    }
+}
+
+mod const_default {
+    use const_assoc::{Assoc, ConstDefault, PrimitiveEnum};
+
+    #[repr(u8)]
+    #[derive(Copy, Clone, Debug, PrimitiveEnum)]
+    enum Slot {
+        A,
+        B,
+    }
+
+    #[repr(u8)]
+    #[derive(Copy, Clone, Debug, PartialEq, PrimitiveEnum)]
+    #[primitive_enum(const_default)]
+    enum State {
+        Idle,
+        Running,
+    }
+
+    #[test]
+    fn zero_variant_is_default() {
+        const STATES: Assoc<Slot, State> = Assoc::DEFAULT;
+
+        assert_eq!(State::DEFAULT, State::Idle);
+        assert_eq!(STATES, [State::Idle, State::Idle]);
+        assert_ne!(STATES[Slot::A], State::Running);
+        assert_ne!(STATES[Slot::B], State::Running);
+    }
+
+    #[test]
+    fn default_ignores_declaration_order() {
+        #[repr(u8)]
+        #[derive(Copy, Clone, Debug, PartialEq, PrimitiveEnum)]
+        #[primitive_enum(const_default)]
+        enum Reordered {
+            Last = 1,
+            First = 0,
+        }
+
+        assert_eq!(Reordered::DEFAULT, Reordered::First);
+        assert_ne!(Reordered::DEFAULT, Reordered::Last);
+    }
+}
+
+#[test]
+fn variants_matches_map_len() {
+    #[allow(dead_code)]
+    #[repr(u8)]
+    #[derive(Copy, Clone, PrimitiveEnum)]
+    enum Direction {
+        North,
+        East,
+        South,
+        West,
+    }
+
+    assert_eq!(Direction::VARIANTS, 4);
+    const { assert!(Direction::IS_CONTIGUOUS) };
+    assert_eq!(
+        const_assoc::Assoc::<Direction, ()>::LEN,
+        Direction::VARIANTS
+    );
+}
+
+#[test]
+fn non_exhaustive_enum() {
+    #[non_exhaustive]
+    #[repr(u8)]
+    #[derive(Copy, Clone, PrimitiveEnum)]
+    enum Level {
+        Low,
+        High,
+    }
+
+    let levels = assoc! {
+        Level::Low => 1,
+        Level::High => 2,
+    };
+
+    assert_eq!(Level::VARIANTS, 2);
+    assert_eq!(levels[Level::High], 2);
+}
+
+#[test]
+fn from_repr() {
+    #[repr(u16)]
+    #[derive(Copy, Clone, Debug, PartialEq, PrimitiveEnum)]
+    #[primitive_enum(from_repr)]
+    enum Phase {
+        Start,
+        Middle,
+        End,
+    }
+
+    const MIDDLE: Option<Phase> = Phase::from_repr(1);
+
+    assert_eq!(Phase::from_repr(0), Some(Phase::Start));
+    assert_eq!(MIDDLE, Some(Phase::Middle));
+    assert_eq!(Phase::from_repr(2), Some(Phase::End));
+    assert_eq!(Phase::from_repr(3), None);
+}
+
+#[test]
+fn from_repr_is_opt_in() {
+    #[repr(u8)]
+    #[derive(Copy, Clone, Debug, PartialEq, PrimitiveEnum)]
+    enum Custom {
+        Zero,
+        One,
+    }
+
+    impl Custom {
+        fn from_repr(value: u8) -> Self {
+            if value == 0 {
+                Custom::Zero
+            } else {
+                Custom::One
+            }
+        }
+    }
+
+    assert_eq!(Custom::from_repr(7), Custom::One);
+    assert_eq!(Custom::from_repr(0), Custom::Zero);
+}
+
+#[test]
+fn explicit_discriminants() {
+    #[repr(u8)]
+    #[derive(Copy, Clone, Debug, PartialEq, PrimitiveEnum)]
+    enum Shuffled {
+        C = 1 << 1,
+        A = 0,
+        B = Shuffled::A as u8 + 1,
+    }
+
+    #[repr(u32)]
+    #[derive(Copy, Clone, Debug, PartialEq, PrimitiveEnum)]
+    #[primitive_enum(from_repr)]
+    enum Implicit {
+        First = (8 - 2) * 2 - 12,
+        Second,
+        Third = 0b10,
+    }
+
+    let shuffled = assoc! {
+        Shuffled::A => 'a',
+        Shuffled::B => 'b',
+        Shuffled::C => 'c',
+    };
+    assert_eq!(
+        shuffled.keys().collect::<Vec<_>>(),
+        [Shuffled::A, Shuffled::B, Shuffled::C]
+    );
+    assert_eq!(shuffled[Shuffled::C], 'c');
+
+    assert_eq!(Implicit::VARIANTS, 3);
+    assert_eq!(Implicit::from_repr(1), Some(Implicit::Second));
+    assert_eq!(Implicit::from_repr(2), Some(Implicit::Third));
+    assert_eq!(Implicit::First as u32, 0);
+}
+
+#[repr(u8)]
+#[derive(Copy, Clone)]
+enum Unsound {
+    A,
+    B,
+}
+
+// Intentionally wrong: `Unsound::B` has discriminant 1, which is not less than
+// `MAX_VARIANTS`.
+unsafe impl PrimitiveEnum for Unsound {
+    type Layout = const_assoc::PrimitiveEnumLayout<u8, 1>;
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "its `PrimitiveEnum` implementation is unsound")]
+fn assoc_macro_rejects_out_of_range_keys() {
+    let _ = assoc! {
+        Unsound::B => (),
+    };
+}
+
+#[test]
+fn checked_get_rejects_out_of_range_keys() {
+    let map: Assoc<Unsound, u8> = Assoc::from_values([7]);
+    assert_eq!(map.checked_get(Unsound::A), Some(&7));
+    assert_eq!(map.checked_get(Unsound::B), None);
+}
+
+#[test]
+fn repr_from_attribute_option() {
+    #[derive(Copy, Clone, Debug, PartialEq, PrimitiveEnum)]
+    #[primitive_enum(unsafe_repr = u8, variants = 3, from_repr)]
+    enum Plain {
+        A,
+        B,
+        C,
+    }
+
+    let map = assoc! {
+        Plain::A => 1,
+        Plain::B => 2,
+        Plain::C => 3,
+    };
+
+    assert_eq!(Plain::VARIANTS, 3);
+    assert_eq!(map[Plain::B], 2);
+    assert_eq!(Plain::from_repr(2), Some(Plain::C));
+}
+
+#[test]
+#[should_panic(expected = "invalid discriminant for key type")]
+fn const_get_rejects_out_of_range_keys() {
+    let map: Assoc<Unsound, u8> = Assoc::from_values([7]);
+    assert_eq!(map.const_get(Unsound::A), &7);
+    let _ = map.const_get(Unsound::B);
+}
+
+#[test]
+fn complex_discriminants_with_variant_count() {
+    const fn one() -> u8 {
+        1
+    }
+
+    #[repr(u8)]
+    #[derive(Copy, Clone, Debug, PartialEq, PrimitiveEnum)]
+    #[primitive_enum(variants = 3)]
+    enum Computed {
+        B = one(),
+        C,
+        A = 0,
+    }
+
+    let map = assoc! {
+        Computed::A => 'a',
+        Computed::B => 'b',
+        Computed::C => 'c',
+    };
+
+    assert_eq!(
+        map.keys().collect::<Vec<_>>(),
+        [Computed::A, Computed::B, Computed::C]
+    );
+}
+
+#[test]
+fn truncating_casts_in_discriminants() {
+    #[repr(u8)]
+    #[derive(Copy, Clone, Debug, PartialEq, PrimitiveEnum)]
+    enum Wrapped {
+        A = 256u16 as u8,
+        B,
+    }
+
+    let map = assoc! {
+        Wrapped::A => 1,
+        Wrapped::B => 2,
+    };
+
+    assert_eq!(Wrapped::VARIANTS, 2);
+    assert_eq!(map[Wrapped::B], 2);
+}
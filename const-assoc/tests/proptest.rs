@@ -0,0 +1,27 @@
+#![cfg(feature = "proptest")]
+
+use const_assoc::proptest::{any_assoc, assoc_of};
+use const_assoc::{Assoc, PrimitiveEnum};
+use proptest::prelude::*;
+
+#[repr(u8)]
+#[derive(Copy, Clone, Debug, PrimitiveEnum)]
+enum Letter {
+    A,
+    B,
+    C,
+}
+
+proptest! {
+    #[test]
+    fn generated_values_respect_strategy(map in assoc_of::<Letter, _, 3>(0u32..10)) {
+        prop_assert!(map.values().all(|value| *value < 10));
+    }
+
+    #[test]
+    fn generates_arbitrary_maps(map in any_assoc::<Letter, u8, 3>()) {
+        let map: Assoc<Letter, u8> = map;
+        prop_assert_eq!(map.values().count(), map.len());
+        let _ = (map[Letter::A], map[Letter::B], map[Letter::C]);
+    }
+}
@@ -0,0 +1,36 @@
+#![cfg(feature = "rayon")]
+
+use const_assoc::{assoc, PrimitiveEnum};
+use rayon::prelude::*;
+
+#[repr(u8)]
+#[derive(Copy, Clone, Debug, PartialEq, PrimitiveEnum)]
+enum Worker {
+    First,
+    Second,
+    Third,
+}
+
+#[test]
+fn parallel_iteration() {
+    let mut jobs = assoc! {
+        Worker::First => 1u64,
+        Worker::Second => 2u64,
+        Worker::Third => 3u64,
+    };
+
+    jobs.par_values_mut().for_each(|job| *job *= 10);
+    assert_eq!(jobs.par_values().sum::<u64>(), 60);
+
+    let mut entries: Vec<_> = jobs.into_par_iter().collect();
+    entries.sort_by_key(|(_, job)| *job);
+
+    assert_eq!(
+        entries,
+        [
+            (Worker::First, 10),
+            (Worker::Second, 20),
+            (Worker::Third, 30)
+        ]
+    );
+}
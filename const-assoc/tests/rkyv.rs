@@ -0,0 +1,31 @@
+#![cfg(feature = "rkyv")]
+
+use const_assoc::{assoc, Assoc, PrimitiveEnum};
+use rkyv::rancor::Error;
+
+#[repr(u8)]
+#[derive(Copy, Clone, Debug, PrimitiveEnum)]
+enum Level {
+    Low,
+    Medium,
+    High,
+}
+
+#[test]
+fn archived_map_is_accessible_by_key() {
+    let thresholds = assoc! {
+        Level::Low => 10u32,
+        Level::Medium => 100u32,
+        Level::High => 1000u32,
+    };
+
+    let bytes = rkyv::to_bytes::<Error>(&thresholds).unwrap();
+    let archived = rkyv::access::<Assoc<Level, rkyv::Archived<u32>>, Error>(&bytes).unwrap();
+
+    assert_eq!(archived[Level::Low], 10);
+    assert_eq!(archived[Level::Medium], 100);
+    assert_eq!(archived[Level::High], 1000);
+
+    let deserialized: Assoc<Level, u32> = rkyv::deserialize::<_, Error>(archived).unwrap();
+    assert_eq!(deserialized, thresholds);
+}
@@ -0,0 +1,10 @@
+use const_assoc::PrimitiveEnum;
+
+#[repr(align(4), u8)]
+#[derive(Copy, Clone, PrimitiveEnum)]
+enum Aligned {
+    A,
+    B,
+}
+
+fn main() {}
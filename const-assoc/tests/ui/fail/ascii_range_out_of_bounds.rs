@@ -0,0 +1,13 @@
+use const_assoc::{assoc_from_fn, AsciiRange, Assoc};
+
+type Wide = AsciiRange<120, 20>;
+
+const fn zero(_: Wide) -> u8 {
+    0
+}
+
+const TABLE: Assoc<Wide, u8> = assoc_from_fn!(zero);
+
+fn main() {
+    let _ = TABLE;
+}
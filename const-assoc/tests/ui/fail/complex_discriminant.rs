@@ -0,0 +1,14 @@
+use const_assoc::PrimitiveEnum;
+
+const fn one() -> u8 {
+    1
+}
+
+#[repr(u8)]
+#[derive(Copy, Clone, PrimitiveEnum)]
+enum Complex {
+    A = one() - 1,
+    B,
+}
+
+fn main() {}
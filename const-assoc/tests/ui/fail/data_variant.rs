@@ -0,0 +1,10 @@
+use const_assoc::PrimitiveEnum;
+
+#[repr(u8)]
+#[derive(Copy, Clone, PrimitiveEnum)]
+enum WithData {
+    A,
+    B(u8),
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+use const_assoc::PrimitiveEnum;
+
+#[repr(u8)]
+enum Other {
+    A = 200,
+}
+
+#[repr(u8)]
+#[derive(Copy, Clone, PrimitiveEnum)]
+enum Mixed {
+    A = 0,
+    B = Other::A as u8,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use const_assoc::PrimitiveEnum;
+
+#[repr(u64)]
+#[derive(Copy, Clone, PrimitiveEnum)]
+enum Huge {
+    A = 0,
+    B = 0xFFFF_FFFF_FFFF_FFFF,
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+use const_assoc::PrimitiveEnum;
+
+const fn two() -> u8 {
+    2
+}
+
+#[repr(u8)]
+#[derive(Copy, Clone, PrimitiveEnum)]
+#[primitive_enum(variants = 2)]
+enum Unchecked {
+    A = 0,
+    B = two(),
+}
+
+fn main() {}
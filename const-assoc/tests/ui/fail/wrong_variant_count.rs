@@ -0,0 +1,11 @@
+use const_assoc::PrimitiveEnum;
+
+#[repr(u8)]
+#[derive(Copy, Clone, PrimitiveEnum)]
+#[primitive_enum(variants = 3)]
+enum Plain {
+    A,
+    B,
+}
+
+fn main() {}
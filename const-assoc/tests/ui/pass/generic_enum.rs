@@ -0,0 +1,23 @@
+use const_assoc::{assoc, PrimitiveEnum};
+
+#[repr(u8)]
+#[derive(Copy, Clone, PrimitiveEnum)]
+#[primitive_enum(from_repr)]
+enum Slot<const TAG: usize>
+where
+    [(); TAG]:,
+{
+    First,
+    Second,
+}
+
+fn main() {
+    let slots = assoc! {
+        Slot::<3>::First => 1,
+        Slot::<3>::Second => 2,
+    };
+
+    assert_eq!(slots[Slot::<3>::First], 1);
+    assert_eq!(slots[Slot::<3>::Second], 2);
+    assert!(Slot::<3>::from_repr(1).is_some());
+}